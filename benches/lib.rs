@@ -3,7 +3,19 @@ extern crate test;
 
 #[cfg(test)]
 mod tests {
-    use bioinformatics::sequencing::{global, local};
+    use bioinformatics::formats::{fasta, fastq};
+    use bioinformatics::sequencing::alignment::Operation;
+    use bioinformatics::sequencing::banded::{align_global_banded, align_local_banded, Band};
+    use bioinformatics::sequencing::cigar::Cigar;
+    use bioinformatics::sequencing::edit::{damerau, edit_distance, levenshtein, osa, Edit, Metric};
+    use bioinformatics::sequencing::lcs::{lcs, traceback_lcs};
+    use bioinformatics::sequencing::myers::{myers_distance, myers_search};
+    use bioinformatics::sequencing::packed::PackedDna;
+    use bioinformatics::sequencing::sequence::{reverse_complement, DnaSeq, ProteinSeq, RnaSeq, Strand};
+    use bioinformatics::sequencing::striped::{align_local_striped, Engine, Profile};
+    use bioinformatics::sequencing::substitution::SubstitutionMatrix;
+    use bioinformatics::sequencing::traceback::Traceback;
+    use bioinformatics::sequencing::{global, hamming, local, HammingError};
     use test::Bencher;
 
     #[test]
@@ -12,6 +24,7 @@ mod tests {
             match_: 5,
             mismatch: -3,
             gap: -4,
+            substitution: None,
         };
 
         let (s, _b) = global::align_global("ATATAGGGAGATATAGAGA", "AGAGGGGTTATAAGGGAGAG", &opt);
@@ -25,6 +38,7 @@ mod tests {
             match_: 5,
             mismatch: -3,
             gap: -4,
+            substitution: None,
         };
 
         let (s, _b) = local::align_local("ATATAGGGAGATATAGAGA", "AGAGGGGTTATAAGGGAGAG", &opt);
@@ -33,16 +47,853 @@ mod tests {
         assert_eq!(s.max(), &48);
     }
 
+    #[test]
+    fn traceback_global_test() {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let (s, b) = global::align_global("AAAGATACCA", "GGGACTTAGA", &opt);
+        let alignment = global::traceback_global(&s, &b, "AAAGATACCA", "GGGACTTAGA");
+
+        assert_eq!(alignment.aligned_u, "AAAGA--TACCA");
+        assert_eq!(alignment.aligned_v, "-GGGACTTA-GA");
+        assert_eq!(alignment.score, s[s.row() - 1][s.col() - 1]);
+        assert_eq!((alignment.u_start, alignment.u_end), (0, 10));
+        assert_eq!((alignment.v_start, alignment.v_end), (0, 10));
+        assert_eq!(alignment.operations[0], Operation::Deletion);
+        assert_eq!(alignment.operations[5], Operation::Insertion);
+    }
+
+    #[test]
+    fn traceback_local_test() {
+        let opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let (s, b) = local::align_local("GGAGACCATTATG", "CCAATATG", &opt);
+        let alignment = local::traceback_local(&s, &b, "GGAGACCATTATG", "CCAATATG");
+
+        assert_eq!(alignment.aligned_u, "CCATTATG");
+        assert_eq!(alignment.aligned_v, "CCAATATG");
+        assert_eq!(alignment.score, 32);
+        assert_eq!((alignment.u_start, alignment.u_end), (5, 13));
+        assert_eq!((alignment.v_start, alignment.v_end), (0, 8));
+        assert_eq!(alignment.operations[3], Operation::Mismatch);
+    }
+
+    #[test]
+    fn traceback_lcs_test() {
+        let (s, b) = lcs("ABCBDAB", "BDCABA");
+        let alignment = traceback_lcs(&s, &b, "ABCBDAB", "BDCABA");
+
+        assert_eq!(alignment.score, 4);
+        assert_eq!(alignment.operations.iter().filter(|&&op| op == Operation::Match).count(), 4);
+        assert_eq!(alignment.aligned_u.replace('-', ""), "ABCBDAB");
+        assert_eq!(alignment.aligned_v.replace('-', ""), "BDCABA");
+    }
+
+    #[test]
+    fn traceback_ties_test() {
+        let opt = global::Options {
+            match_: 1,
+            mismatch: -1,
+            gap: -1,
+            substitution: None,
+        };
+
+        let (_s, b) = global::align_global("AAT", "AT", &opt);
+
+        assert_eq!(std::mem::size_of::<Traceback>(), 1);
+        assert_eq!(b[0][0], Traceback::NONE);
+        assert_eq!(b[2][1], Traceback::DIAGONAL | Traceback::UP);
+        assert_eq!(b[2][1].preferred(), Traceback::DIAGONAL);
+
+        let (_s, b) = lcs("AB", "BA");
+
+        assert_eq!(b[2][2], Traceback::UP | Traceback::LEFT);
+        assert_eq!(b[2][2].to_string(), "u");
+
+        let opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let (_s, b) = local::align_local("AC", "GT", &opt);
+
+        assert!(b[1][1].is_none());
+    }
+
+    #[test]
+    fn byte_inputs_test() {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let u = random_sequence(40, 3);
+        let v = random_sequence(35, 4);
+
+        let (s, b) = global::align_global(&u, &v, &opt);
+        let expected = global::traceback_global(&s, &b, &u, &v);
+
+        let v_bytes = v.clone().into_bytes();
+
+        let (s, b) = global::align_global(u.as_bytes(), &v_bytes, &opt);
+        let alignment = global::traceback_global(&s, &b, u.as_bytes(), v_bytes);
+
+        assert_eq!(alignment, expected);
+
+        let (s, b) = lcs(b"ABCBDAB", b"BDCABA");
+        assert_eq!(traceback_lcs(&s, &b, b"ABCBDAB", b"BDCABA").score, 4);
+
+        assert_eq!(hamming(b"ATGAT", "TTAGT"), Ok(3));
+        assert!(hamming("Aé", "AE").is_err());
+    }
+
+    #[test]
+    fn sequence_types_test() {
+        let dna = DnaSeq::new("acgtRYSWKMBDHVN").unwrap();
+
+        assert_eq!(dna.as_bytes(), b"ACGTRYSWKMBDHVN");
+        assert!(dna.is_ambiguous());
+        assert!(!"ACGT".parse::<DnaSeq>().unwrap().is_ambiguous());
+
+        assert_eq!(DnaSeq::new("ACGU").unwrap_err(), "Invalid DNA residue `U` at position 3");
+        assert_eq!(RnaSeq::new("ACGT").unwrap_err(), "Invalid RNA residue `T` at position 3");
+        assert_eq!(DnaSeq::new("AC GT").unwrap_err(), "Invalid DNA residue ` ` at position 2");
+        assert!(ProteinSeq::new("MKV*").is_ok());
+        assert!(ProteinSeq::new("MKV1").is_err());
+
+        let opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let u = DnaSeq::new("ggagaccattatg").unwrap();
+        let v = DnaSeq::new("ccaatatg").unwrap();
+
+        let (s, b) = local::align_local(&u, &v, &opt);
+
+        assert_eq!(local::traceback_local(&s, &b, &u, &v).aligned_u, "CCATTATG");
+    }
+
+    #[test]
+    fn align_stranded_test() {
+        let dna = DnaSeq::new("ACGTRYKMBVDHSWN").unwrap();
+
+        assert_eq!(dna.reverse_complement().as_bytes(), b"NWSDHBVKMRYACGT");
+        assert_eq!(dna.reverse_complement().reverse_complement(), dna);
+        assert_eq!(RnaSeq::new("ACGU").unwrap().reverse_complement().as_bytes(), b"ACGU");
+        assert_eq!(reverse_complement("acgTU"), b"AAcgt");
+
+        let global_opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let local_opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        for seed in 0..20 {
+            let v = random_sequence(60, seed);
+            let read = &v[10 + seed as usize..40];
+
+            let (alignment, strand) = global::align_global_stranded(read, &v, &global_opt, global::Mode::Fitting);
+
+            assert_eq!(strand, Strand::Forward);
+            assert_eq!(alignment.aligned_u, read);
+
+            let (alignment, strand) = global::align_global_stranded(reverse_complement(read), &v, &global_opt, global::Mode::Fitting);
+
+            assert_eq!(strand, Strand::Reverse);
+            assert_eq!(alignment.aligned_u, read);
+            assert_eq!((alignment.v_start, alignment.v_end), (10 + seed as usize, 40));
+
+            let (alignment, strand) = local::align_local_stranded(reverse_complement(read), &v, &local_opt);
+
+            assert_eq!(strand, Strand::Reverse);
+            assert_eq!(alignment.score, 5 * read.len() as i32);
+        }
+    }
+
+    /// Returns a pseudo-random nucleotide sequence with the specified length.
+    fn random_sequence(len: usize, seed: u64) -> String {
+        let mut state = seed;
+
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                ['A', 'C', 'G', 'T'][(state >> 62) as usize]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn align_global_hirschberg_test() {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        for seed in 0..20 {
+            let u = random_sequence(10 + seed as usize * 3, seed);
+            let v = random_sequence(25 - seed as usize, seed + 100);
+
+            let (s, _b) = global::align_global(&u, &v, &opt);
+            let alignment = global::align_global_hirschberg(&u, &v, &opt);
+
+            assert_eq!(alignment.score, s[s.row() - 1][s.col() - 1]);
+            assert_eq!(alignment.aligned_u.replace('-', ""), u);
+            assert_eq!(alignment.aligned_v.replace('-', ""), v);
+        }
+    }
+
+    #[test]
+    fn align_global_hirschberg_empty_test() {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        assert_eq!(global::align_global_hirschberg("", "ACG", &opt).aligned_u, "---");
+        assert_eq!(global::align_global_hirschberg("ACG", "", &opt).score, -12);
+        assert_eq!(global::align_global_hirschberg("", "", &opt).score, 0);
+    }
+
+    #[test]
+    fn align_global_banded_test() {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        for seed in 0..20 {
+            let u = random_sequence(10 + seed as usize * 3, seed);
+            let v = random_sequence(25 - seed as usize, seed + 100);
+
+            let (s, _b) = global::align_global(&u, &v, &opt);
+            let alignment = align_global_banded(&u, &v, &opt, &Band { width: 1, widen: true });
+
+            assert_eq!(alignment.score, s[s.row() - 1][s.col() - 1]);
+            assert_eq!(alignment.aligned_u.replace('-', ""), u);
+            assert_eq!(alignment.aligned_v.replace('-', ""), v);
+        }
+    }
+
+    #[test]
+    fn align_global_banded_narrow_test() {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let u = random_sequence(200, 7);
+        let v = format!("{}{}", &u[..120], &u[121..]);
+
+        let (s, _b) = global::align_global(&u, &v, &opt);
+        let alignment = align_global_banded(&u, &v, &opt, &Band { width: 0, widen: false });
+
+        assert_eq!(alignment.score, s[s.row() - 1][s.col() - 1]);
+    }
+
+    #[test]
+    fn align_local_banded_test() {
+        let opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        for seed in 0..20 {
+            let u = random_sequence(10 + seed as usize * 3, seed);
+            let v = random_sequence(25 - seed as usize, seed + 100);
+
+            let (s, _b) = local::align_local(&u, &v, &opt);
+            let alignment = align_local_banded(&u, &v, &opt, &Band { width: 1, widen: true });
+
+            assert_eq!(alignment.score, *s.max());
+        }
+    }
+
+    #[test]
+    fn align_banded_positive_gap_test() {
+        let opt = local::Options {
+            match_: 5,
+            mismatch: -1,
+            gap: 2,
+            substitution: None,
+        };
+
+        let alignment = align_local_banded("GTCC", "ACACTATT", &opt, &Band { width: 0, widen: true });
+
+        assert_eq!(alignment.score, 24);
+
+        let global_opt = global::Options {
+            match_: 5,
+            mismatch: -1,
+            gap: 2,
+            substitution: None,
+        };
+
+        for seed in 0..20 {
+            let u = random_sequence(4 + seed as usize % 7, seed);
+            let v = random_sequence(9 - seed as usize % 5, seed + 100);
+
+            let (s, _b) = local::align_local(&u, &v, &opt);
+            let alignment = align_local_banded(&u, &v, &opt, &Band { width: 0, widen: true });
+
+            assert_eq!(alignment.score, *s.max());
+
+            let (s, _b) = global::align_global(&u, &v, &global_opt);
+            let alignment = align_global_banded(&u, &v, &global_opt, &Band { width: 0, widen: true });
+
+            assert_eq!(alignment.score, s[s.row() - 1][s.col() - 1]);
+        }
+    }
+
+    #[test]
+    fn traceback_global_cooptimal_test() {
+        let opt = global::Options {
+            match_: 1,
+            mismatch: -1,
+            gap: -1,
+            substitution: None,
+        };
+
+        for seed in 0..20 {
+            let u = random_sequence(8 + seed as usize % 5, seed);
+            let v = random_sequence(10 - seed as usize % 4, seed + 100);
+
+            let (s, b) = global::align_global(&u, &v, &opt);
+
+            let alignments = global::traceback_global_cooptimal(&s, &b, &u, &v, None).collect::<Vec<_>>();
+
+            assert_eq!(alignments[0], global::traceback_global(&s, &b, &u, &v));
+
+            for (k, alignment) in alignments.iter().enumerate() {
+                let score = alignment
+                    .operations
+                    .iter()
+                    .map(|op| match op {
+                        Operation::Match => 1,
+                        _ => -1,
+                    })
+                    .sum::<i32>();
+
+                assert_eq!(score, alignment.score);
+                assert!(!alignments[..k].contains(alignment));
+            }
+
+            let limited = global::traceback_global_cooptimal(&s, &b, &u, &v, Some(2)).count();
+            assert_eq!(limited, alignments.len().min(2));
+        }
+    }
+
+    #[test]
+    fn align_local_suboptimal_test() {
+        let opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        for seed in 0..10 {
+            let u = random_sequence(40, seed);
+            let v = random_sequence(30, seed + 100);
+
+            let (s, b) = local::align_local(&u, &v, &opt);
+            let alignments = local::align_local_suboptimal(&u, &v, &opt, 5);
+
+            assert_eq!(alignments[0], local::traceback_local(&s, &b, &u, &v));
+
+            for pair in alignments.windows(2) {
+                assert!(pair[0].score >= pair[1].score);
+            }
+        }
+
+        assert!(local::align_local_suboptimal("AAAA", "CCCC", &opt, 3).is_empty());
+    }
+
+    #[test]
+    fn traceback_local_cooptimal_test() {
+        let opt = local::Options {
+            match_: 1,
+            mismatch: -1,
+            gap: -1,
+            substitution: None,
+        };
+
+        let (s, b) = local::align_local("AATT", "AT", &opt);
+        let alignments = local::traceback_local_cooptimal(&s, &b, "AATT", "AT", None).collect::<Vec<_>>();
+
+        assert_eq!(alignments.len(), 1);
+        assert_eq!((alignments[0].u_start, alignments[0].u_end), (1, 3));
+
+        let (s, b) = local::align_local("CCCC", "GG", &opt);
+        let alignments = local::traceback_local_cooptimal(&s, &b, "CCCC", "GG", None).collect::<Vec<_>>();
+
+        assert_eq!(alignments.len(), 1);
+        assert_eq!(alignments[0].score, 0);
+        assert!(alignments[0].operations.is_empty());
+
+        // The mismatch scoring zero is kept in the path, as in the single traceback.
+        let opt = local::Options {
+            match_: 5,
+            mismatch: -5,
+            gap: -10,
+            substitution: None,
+        };
+
+        let (s, b) = local::align_local("AGCC", "ATCC", &opt);
+        let alignments = local::traceback_local_cooptimal(&s, &b, "AGCC", "ATCC", None).collect::<Vec<_>>();
+
+        assert_eq!(alignments[0], local::traceback_local(&s, &b, "AGCC", "ATCC"));
+        assert_eq!(alignments[0].aligned_u, "AGCC");
+
+        for seed in 0..20 {
+            let u = random_sequence(12, seed);
+            let v = random_sequence(10, seed + 100);
+
+            let (s, b) = local::align_local(&u, &v, &opt);
+            let mut alignments = local::traceback_local_cooptimal(&s, &b, &u, &v, None);
+
+            assert_eq!(alignments.next(), Some(local::traceback_local(&s, &b, &u, &v)));
+        }
+    }
+
+    #[test]
+    fn cigar_round_trip_test() {
+        let opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        for seed in 0..20 {
+            let u = random_sequence(30, seed);
+            let v = random_sequence(20, seed + 100);
+
+            let (s, b) = local::align_local(&u, &v, &opt);
+            let alignment = local::traceback_local(&s, &b, &u, &v);
+
+            for extended in [true, false] {
+                let cigar = Cigar::parse(&Cigar::from_alignment(&alignment, v.len(), extended).to_string()).unwrap();
+                let rebuilt = cigar.to_alignment(&u, &v, alignment.u_start).unwrap();
+
+                assert_eq!(rebuilt.aligned_u, alignment.aligned_u);
+                assert_eq!(rebuilt.aligned_v, alignment.aligned_v);
+                assert_eq!(rebuilt.operations, alignment.operations);
+                assert_eq!((rebuilt.v_start, rebuilt.v_end), (alignment.v_start, alignment.v_end));
+            }
+        }
+
+        let (s, b) = global::align_global("AAAGATACCA", "GGGACTTAGA", &global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        });
+        let alignment = global::traceback_global(&s, &b, "AAAGATACCA", "GGGACTTAGA");
+
+        assert_eq!(Cigar::from_alignment(&alignment, 10, true).to_string(), "1D2X2=2I2=1D1X1=");
+        assert_eq!(Cigar::from_alignment(&alignment, 10, false).to_string(), "1D4M2I2M1D2M");
+    }
+
+    #[test]
+    fn cigar_error_test() {
+        assert!(Cigar::parse("M").is_err());
+        assert!(Cigar::parse("0M").is_err());
+        assert!(Cigar::parse("3M2").is_err());
+        assert!(Cigar::parse("3N").is_err());
+        assert_eq!(Cigar::parse("*").unwrap(), Cigar::default());
+
+        assert_eq!(
+            Cigar::parse("3=").unwrap().to_alignment("ACG", "ACT", 0),
+            Err(String::from("Residues `G` and `T` are not equal"))
+        );
+        assert!(Cigar::parse("3X").unwrap().to_alignment("ACG", "ACT", 0).is_err());
+        assert!(Cigar::parse("2M").unwrap().to_alignment("ACG", "ACT", 0).is_err());
+        assert!(Cigar::parse("4M").unwrap().to_alignment("ACG", "ACGT", 0).is_err());
+        assert!(Cigar::parse("1M1S1M").unwrap().to_alignment("ACG", "ACG", 0).is_err());
+        assert!(Cigar::parse("1S2M").unwrap().to_alignment("ACG", "ACG", 1).is_ok());
+    }
+
+    #[test]
+    fn fasta_reader_test() {
+        let text = "\n>seq1 first  record \nacgt\nAC GT\n\n>seq2\r\nTTTT\r\n>seq3\n";
+
+        let records = fasta::Reader::new(text.as_bytes()).collect::<Result<Vec<_>, String>>().unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].id, "seq1");
+        assert_eq!(records[0].description.as_deref(), Some("first  record"));
+        assert_eq!(records[0].sequence, "ACGTACGT");
+        assert_eq!(records[1].description, None);
+        assert_eq!(records[1].sequence, "TTTT");
+        assert_eq!(records[2].sequence, "");
+
+        assert_eq!(hamming(&records[0].sequence[..4], &records[1].sequence), Ok(3));
+    }
+
+    #[test]
+    fn fasta_reader_error_test() {
+        let first = |text: &str| fasta::Reader::new(text.as_bytes()).next().unwrap();
+
+        assert!(first("ACGT\n>seq1\nACGT\n").is_err());
+        assert!(first(">\nACGT\n").is_err());
+        assert!(first(">seq1\nAC1T\n").is_err());
+        assert!(fasta::Reader::new("".as_bytes()).next().is_none());
+    }
+
+    #[test]
+    fn fasta_writer_test() {
+        let records = vec![
+            fasta::Record {
+                id: String::from("seq1"),
+                description: Some(String::from("first record")),
+                sequence: random_sequence(130, 1),
+            },
+            fasta::Record {
+                id: String::from("seq2"),
+                description: None,
+                sequence: random_sequence(60, 2),
+            },
+        ];
+
+        for width in [0, 60, 7] {
+            let mut writer = fasta::Writer::with_line_width(Vec::new(), width);
+
+            for record in records.iter() {
+                writer.write(record).unwrap();
+            }
+
+            let bytes = writer.into_inner();
+            let read = fasta::Reader::new(bytes.as_slice()).collect::<Result<Vec<_>, String>>().unwrap();
+
+            assert_eq!(read, records);
+        }
+    }
+
+    #[test]
+    fn fastq_reader_test() {
+        let text = "@read1 lane 1\nacgt\n+read1 lane 1\nhhBh\n\n@read2\r\nAAAA\r\n+\r\n@@@@\r\n";
+
+        let records = fastq::Reader::new(text.as_bytes(), fastq::Encoding::Phred64)
+            .collect::<Result<Vec<_>, String>>()
+            .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].sequence, "ACGT");
+        assert_eq!(records[0].description.as_deref(), Some("lane 1"));
+        assert_eq!(records[0].qualities, vec![40, 40, 2, 40]);
+        assert_eq!(records[1].qualities, vec![0, 0, 0, 0]);
+
+        let mut writer = fastq::Writer::new(Vec::new(), fastq::Encoding::Phred33);
+
+        for record in records.iter() {
+            writer.write(record).unwrap();
+        }
+
+        let bytes = writer.into_inner();
+        let read = fastq::Reader::new(bytes.as_slice(), fastq::Encoding::Phred33)
+            .collect::<Result<Vec<_>, String>>()
+            .unwrap();
+
+        assert_eq!(read, records);
+    }
+
+    #[test]
+    fn fastq_reader_error_test() {
+        let first = |text: &str| fastq::Reader::new(text.as_bytes(), fastq::Encoding::Phred33).next().unwrap();
+
+        assert!(first(">read1\nACGT\n+\nIIII\n").is_err());
+        assert!(first("@read1\nACGT\n+\nIII\n").is_err());
+        assert!(first("@read1\nACGT\nIIII\n").is_err());
+        assert!(first("@read1\nACGT\n+read2\nIIII\n").is_err());
+        assert!(first("@read1\nACGT\n+\n").is_err());
+        assert!(first("@read1\nACGT\n+\nII I\n").is_err());
+        assert!(fastq::Reader::new("@read1\nACGT\n+\n!!!!\n".as_bytes(), fastq::Encoding::Phred64).next().unwrap().is_err());
+    }
+
+    #[test]
+    fn fastq_trim_test() {
+        let record = fastq::Record {
+            id: String::from("read1"),
+            description: None,
+            sequence: String::from("ACGTACGTAC"),
+            qualities: vec![5, 30, 30, 30, 30, 30, 2, 30, 2, 2],
+        };
+
+        let mut ends = record.clone();
+        ends.trim_ends(20);
+        assert_eq!(ends.sequence, "CGTACGT");
+
+        let mut bwa = record.clone();
+        bwa.trim_bwa(20);
+        assert_eq!(bwa.sequence, "ACGTAC");
+        assert_eq!(bwa.qualities.len(), 6);
+
+        let mut all = record.clone();
+        all.trim_ends(40);
+        assert!(all.sequence.is_empty() && all.qualities.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn align_batch_parallel_test() {
+        use bioinformatics::sequencing::parallel::{align_global_batch, align_local_batch};
+
+        let global_opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+        let local_opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let pairs = (0..50)
+            .map(|seed| (random_sequence(5 + seed as usize, seed), random_sequence(40 - seed as usize / 2, seed + 100)))
+            .collect::<Vec<_>>();
+
+        for threads in [0, 1, 3, 8] {
+            let global_alignments = align_global_batch(&pairs, &global_opt, threads);
+            let local_alignments = align_local_batch(&pairs, &local_opt, threads);
+
+            for (k, (u, v)) in pairs.iter().enumerate() {
+                let (s, b) = global::align_global(u, v, &global_opt);
+                assert_eq!(global_alignments[k], global::traceback_global(&s, &b, u, v));
+
+                let (s, b) = local::align_local(u, v, &local_opt);
+                assert_eq!(local_alignments[k], local::traceback_local(&s, &b, u, v));
+            }
+        }
+
+        assert!(align_global_batch::<&str, &str>(&[], &global_opt, 4).is_empty());
+    }
+
+    #[test]
+    fn align_global_mode_test() {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let (s, b) = global::align_global_mode("GATTA", "CCCGATTACCC", &opt, global::Mode::Fitting);
+        let alignment = global::traceback_global_mode(&s, &b, "GATTA", "CCCGATTACCC", global::Mode::Fitting);
+
+        assert_eq!(alignment.aligned_u, "GATTA");
+        assert_eq!(alignment.aligned_v, "GATTA");
+        assert_eq!(alignment.score, 25);
+        assert_eq!((alignment.v_start, alignment.v_end), (3, 8));
+
+        let (s, b) = global::align_global_mode("TTTTACGTA", "ACGTAGGGG", &opt, global::Mode::Overlap);
+        let alignment = global::traceback_global_mode(&s, &b, "TTTTACGTA", "ACGTAGGGG", global::Mode::Overlap);
+
+        assert_eq!(alignment.score, 25);
+        assert_eq!((alignment.u_start, alignment.u_end), (4, 9));
+        assert_eq!((alignment.v_start, alignment.v_end), (0, 5));
+
+        let (s, b) = global::align_global_mode("CCCGATTA", "GATTACCC", &opt, global::Mode::SemiGlobal);
+        let alignment = global::traceback_global_mode(&s, &b, "CCCGATTA", "GATTACCC", global::Mode::SemiGlobal);
+
+        assert_eq!(alignment.aligned_u, "GATTA");
+        assert_eq!(alignment.score, 25);
+
+        let (s, b) = global::align_global_mode("AAAGATACCA", "GGGACTTAGA", &opt, global::Mode::Global);
+        let alignment = global::traceback_global_mode(&s, &b, "AAAGATACCA", "GGGACTTAGA", global::Mode::Global);
+
+        assert_eq!(alignment.aligned_u, "AAAGA--TACCA");
+        assert_eq!(alignment.aligned_v, "-GGGACTTA-GA");
+    }
+
+    #[test]
+    fn align_global_affine_test() {
+        let opt = global::AffineOptions {
+            match_: 5,
+            mismatch: -3,
+            gap_open: -10,
+            gap_extend: -1,
+            substitution: None,
+        };
+
+        let (s, _b) = global::align_global_affine("ACGTTTTACG", "ACGACG", &opt);
+
+        assert_eq!(s[s.row() - 1][s.col() - 1], 17);
+    }
+
+    #[test]
+    fn align_global_affine_linear_test() {
+        let opt = global::AffineOptions {
+            match_: 5,
+            mismatch: -3,
+            gap_open: -4,
+            gap_extend: -4,
+            substitution: None,
+        };
+
+        let (s, _b) = global::align_global_affine("ATATAGGGAGATATAGAGA", "AGAGGGGTTATAAGGGAGAG", &opt);
+
+        assert_eq!(s[s.row() - 1][s.col() - 1], 36);
+    }
+
+    #[test]
+    fn align_local_affine_test() {
+        let opt = local::AffineOptions {
+            match_: 5,
+            mismatch: -3,
+            gap_open: -10,
+            gap_extend: -1,
+            substitution: None,
+        };
+
+        let (s, _b) = local::align_local_affine("TTGCATCGAAAAGCTATT", "CCGCATCGGCTACC", &opt);
+
+        assert_eq!(s.max(), &37);
+    }
+
+    #[test]
+    fn align_local_affine_linear_test() {
+        let opt = local::AffineOptions {
+            match_: 5,
+            mismatch: -3,
+            gap_open: -4,
+            gap_extend: -4,
+            substitution: None,
+        };
+
+        let (s, _b) = local::align_local_affine("ATATAGGGAGATATAGAGA", "AGAGGGGTTATAAGGGAGAG", &opt);
+
+        assert_eq!(s.max(), &48);
+    }
+
+    #[test]
+    fn align_global_blosum62_test() {
+        let opt = global::Options {
+            match_: 0,
+            mismatch: 0,
+            gap: -8,
+            substitution: Some(SubstitutionMatrix::blosum62()),
+        };
+
+        let (s, _b) = global::align_global("HEAGAWGHEE", "PAWHEAE", &opt);
+
+        assert_eq!(s[s.row() - 1][s.col() - 1], -8);
+    }
+
+    #[test]
+    fn align_local_blosum62_test() {
+        let opt = local::Options {
+            match_: 0,
+            mismatch: 0,
+            gap: -8,
+            substitution: Some(SubstitutionMatrix::blosum62()),
+        };
+
+        let (s, _b) = local::align_local("HEAGAWGHEE", "PAWHEAE", &opt);
+
+        assert_eq!(s.max(), &20);
+    }
+
+    #[test]
+    fn substitution_matrices_test() {
+        for name in ["BLOSUM45", "BLOSUM62", "BLOSUM80", "PAM30", "PAM70", "PAM250"] {
+            let m = SubstitutionMatrix::builtin(name).unwrap();
+
+            assert_eq!(m.alphabet().len(), 24);
+
+            for &a in m.alphabet() {
+                for &b in m.alphabet() {
+                    assert_eq!(m.score(a as u8, b as u8), m.score(b as u8, a as u8));
+                }
+            }
+
+            // The built-in matrices are parsed once, and each call returns a copy.
+            let copy = SubstitutionMatrix::builtin(name).unwrap().clone();
+
+            assert_eq!(format!("{:?}", copy), format!("{:?}", m));
+        }
+    }
+
+    #[test]
+    fn substitution_matrix_parse_error_test() {
+        assert!(SubstitutionMatrix::parse("").is_err());
+        assert!(SubstitutionMatrix::parse("  A  C\nA  1 -1\nC -1").is_err());
+        assert!(SubstitutionMatrix::parse("  A  C\nA  1 -1").is_err());
+        assert!(SubstitutionMatrix::parse("  A  C\nA  1 -1\nG -1  1").is_err());
+        assert!(SubstitutionMatrix::parse("  A  C\nA  1 -1\nC -1  x").is_err());
+    }
+
+    #[bench]
+    fn align_global_bench(b: &mut Bencher) {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        b.iter(|| global::align_global("ATATAGGGAGATATAGAGA", "AGAGGGGTTATAAGGGAGAGGAGT", &opt));
+    }
+
+    #[bench]
+    fn align_global_hirschberg_bench(b: &mut Bencher) {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        b.iter(|| global::align_global_hirschberg("ATATAGGGAGATATAGAGA", "AGAGGGGTTATAAGGGAGAGGAGT", &opt));
+    }
 
     #[bench]
-    fn align_global_bench(b: &mut Bencher) {
+    fn align_global_banded_bench(b: &mut Bencher) {
         let opt = global::Options {
             match_: 5,
             mismatch: -3,
             gap: -4,
+            substitution: None,
         };
 
-        b.iter(|| global::align_global("ATATAGGGAGATATAGAGA", "AGAGGGGTTATAAGGGAGAGGAGT", &opt));
+        b.iter(|| align_global_banded("ATATAGGGAGATATAGAGA", "AGAGGGGTTATAAGGGAGAGGAGT", &opt, &Band { width: 4, widen: true }));
     }
 
     #[bench]
@@ -51,8 +902,787 @@ mod tests {
             match_: 5,
             mismatch: -3,
             gap: -4,
+            substitution: None,
         };
 
         b.iter(|| local::align_local("ATATAGGGAGATATAGAGA", "AGAGGGGTTATAAGGGAGAG", &opt));
     }
+
+    #[test]
+    fn align_local_striped_test() {
+        let engines: Vec<Engine> = [Engine::Avx2, Engine::Sse2, Engine::Portable]
+            .into_iter()
+            .filter(|engine| engine.is_supported())
+            .collect();
+
+        let scores = [(5, -3, -4), (1, -1, -1), (2, -7, 0), (90, -120, -30)];
+
+        for (seed, (match_, mismatch, gap)) in scores.into_iter().enumerate() {
+            let opt = local::Options {
+                match_,
+                mismatch,
+                gap,
+                substitution: None,
+            };
+
+            for k in 0..20 {
+                let seed = (seed * 100 + k) as u64;
+                let u = random_sequence(k * 7, seed);
+                let v = random_sequence(k * 11 + 3, seed + 50);
+                let (s, _b) = local::align_local(&u, &v, &opt);
+
+                for &engine in &engines {
+                    let profile = Profile::with_engine(&u, &opt, engine).unwrap();
+
+                    assert_eq!(profile.score(&v), *s.max());
+                }
+            }
+        }
+
+        let opt = local::Options {
+            match_: 0,
+            mismatch: 0,
+            gap: -8,
+            substitution: Some(SubstitutionMatrix::blosum62()),
+        };
+
+        assert_eq!(align_local_striped("HEAGAWGHEE", "PAWHEAE", &opt), 20);
+    }
+
+    #[test]
+    fn align_local_striped_overflow_test() {
+        let opt = local::Options {
+            match_: 100,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        // The scores saturate the 8-bit lanes and, for the longer sequences,
+        // the 16-bit lanes as well.
+        for len in [2, 200, 1000] {
+            let u = random_sequence(len, 1);
+            let v = format!("{}{}{}", random_sequence(30, 2), u, random_sequence(30, 3));
+            let (s, _b) = local::align_local(&u, &v, &opt);
+
+            assert_eq!(*s.max(), 100 * len as i32);
+
+            for engine in [Engine::Avx2, Engine::Sse2, Engine::Portable] {
+                if let Ok(profile) = Profile::with_engine(&u, &opt, engine) {
+                    assert_eq!(profile.score(&v), *s.max());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn score_only_test() {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+        let local_opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+        let affine = global::AffineOptions {
+            match_: 5,
+            mismatch: -3,
+            gap_open: -10,
+            gap_extend: -1,
+            substitution: None,
+        };
+        let local_affine = local::AffineOptions {
+            match_: 5,
+            mismatch: -3,
+            gap_open: -10,
+            gap_extend: -1,
+            substitution: None,
+        };
+
+        let modes = [
+            global::Mode::Global,
+            global::Mode::SemiGlobal,
+            global::Mode::Fitting,
+            global::Mode::Overlap,
+        ];
+
+        for k in 0..30 {
+            let u = random_sequence(k * 3, k as u64);
+            let v = random_sequence(k * 5 + 1, k as u64 + 100);
+
+            for mode in modes {
+                let (s, b) = global::align_global_mode(&u, &v, &opt, mode);
+                let alignment = global::traceback_global_mode(&s, &b, &u, &v, mode);
+
+                assert_eq!(
+                    global::score_global_mode(&u, &v, &opt, mode),
+                    (alignment.score, alignment.u_end, alignment.v_end)
+                );
+            }
+
+            let (s, b) = local::align_local(&u, &v, &local_opt);
+            let alignment = local::traceback_local(&s, &b, &u, &v);
+
+            assert_eq!(
+                local::score_local(&u, &v, &local_opt),
+                (alignment.score, alignment.u_end, alignment.v_end)
+            );
+
+            let (s, _tb) = global::align_global_affine(&u, &v, &affine);
+
+            assert_eq!(
+                global::score_global_affine(&u, &v, &affine),
+                (s[(s.row() - 1, s.col() - 1)], u.len(), v.len())
+            );
+
+            let (s, tb) = local::align_local_affine(&u, &v, &local_affine);
+            let alignment = local::traceback_local_affine(&s, &tb, &u, &v);
+
+            assert_eq!(
+                local::score_local_affine(&u, &v, &local_affine),
+                (alignment.score, alignment.u_end, alignment.v_end)
+            );
+        }
+    }
+
+    #[test]
+    fn edit_distance_test() {
+        assert_eq!(levenshtein("KITTEN", "SITTING"), 3);
+        assert_eq!(levenshtein("", "ACGT"), 4);
+        assert_eq!(levenshtein("ACGT", "ACGT"), 0);
+
+        assert_eq!(osa("ACGT", "CAGT"), 1);
+        assert_eq!(damerau("ACGT", "CAGT"), 1);
+        assert_eq!(levenshtein("ACGT", "CAGT"), 2);
+
+        // The optimal string alignment cannot insert between the transposed residues.
+        assert_eq!(osa("CA", "ABC"), 3);
+        assert_eq!(damerau("CA", "ABC"), 2);
+
+        let (distance, script) = edit_distance("CA", "ABC", Metric::Damerau);
+
+        assert_eq!(distance, 2);
+        assert_eq!(script, vec![Edit::Transposition { deleted: 0, inserted: 1 }]);
+
+        let (distance, script) = edit_distance("GATTACA", "GTATCA", Metric::OptimalStringAlignment);
+
+        assert_eq!(distance, 2);
+        assert_eq!(script.iter().map(|e| e.cost()).sum::<usize>(), 2);
+        assert_eq!(script.iter().filter(|&&e| e == Edit::Match).count(), 4);
+
+        for k in 0..20 {
+            let u = random_sequence(k * 3, k as u64);
+            let v = random_sequence(k * 2 + 1, k as u64 + 7);
+
+            let distances = [Metric::Levenshtein, Metric::OptimalStringAlignment, Metric::Damerau]
+                .map(|metric| {
+                    let (distance, script) = edit_distance(&u, &v, metric);
+
+                    assert_eq!(script.iter().map(|e| e.cost()).sum::<usize>(), distance);
+
+                    distance
+                });
+
+            assert!(distances[0] >= distances[1] && distances[1] >= distances[2]);
+            assert_eq!(distances[1], osa_reference(u.as_bytes(), v.as_bytes()));
+            assert_eq!(distances[2], damerau_reference(u.as_bytes(), v.as_bytes()));
+        }
+
+        // The distances that differ between the metrics.
+        for (u, v, lev, opt, dam) in [
+            ("CA", "ABC", 3, 3, 2),
+            ("AB", "BA", 2, 1, 1),
+            ("A CAT", "AN ACT", 3, 2, 2),
+            ("ABCDEF", "BADCFE", 4, 3, 3),
+            ("TEH", "HET", 2, 2, 2),
+            ("ABC", "CA", 3, 3, 2),
+            ("BCA", "ACB", 2, 2, 2),
+        ] {
+            assert_eq!((levenshtein(u, v), osa(u, v), damerau(u, v)), (lev, opt, dam));
+        }
+
+        for k in 0..36 {
+            let u = random_sequence(k % 6, k as u64 + 50);
+            let v = random_sequence(k / 6, k as u64 + 90);
+
+            assert_eq!(damerau(&u, &v), damerau_brute_force(u.as_bytes(), v.as_bytes()));
+            assert_eq!(osa(&u, &v), osa_reference(u.as_bytes(), v.as_bytes()));
+        }
+    }
+
+    /// Returns the optimal string alignment distance between `u` and `v`,
+    /// using the textbook recurrence.
+    fn osa_reference(u: &[u8], v: &[u8]) -> usize {
+        let mut d = vec![vec![0; v.len() + 1]; u.len() + 1];
+
+        for i in 0..=u.len() {
+            for j in 0..=v.len() {
+                d[i][j] = if i == 0 || j == 0 {
+                    i + j
+                } else {
+                    let mut best = (d[i - 1][j] + 1)
+                        .min(d[i][j - 1] + 1)
+                        .min(d[i - 1][j - 1] + usize::from(u[i - 1] != v[j - 1]));
+
+                    if i > 1 && j > 1 && u[i - 1] == v[j - 2] && u[i - 2] == v[j - 1] {
+                        best = best.min(d[i - 2][j - 2] + 1);
+                    }
+
+                    best
+                };
+            }
+        }
+
+        d[u.len()][v.len()]
+    }
+
+    /// Returns the Damerau-Levenshtein distance between `u` and `v`, trying every
+    /// earlier pair of transposed residues instead of only the last one.
+    fn damerau_reference(u: &[u8], v: &[u8]) -> usize {
+        let mut d = vec![vec![0; v.len() + 1]; u.len() + 1];
+
+        for i in 0..=u.len() {
+            for j in 0..=v.len() {
+                if i == 0 || j == 0 {
+                    d[i][j] = i + j;
+                    continue;
+                }
+
+                let mut best = (d[i - 1][j] + 1)
+                    .min(d[i][j - 1] + 1)
+                    .min(d[i - 1][j - 1] + usize::from(u[i - 1] != v[j - 1]));
+
+                // The residues between the transposed ones are deleted from `u`
+                // and inserted from `v`.
+                for k in 1..i {
+                    for l in 1..j {
+                        if u[k - 1] == v[j - 1] && u[i - 1] == v[l - 1] {
+                            best = best.min(d[k - 1][l - 1] + (i - k - 1) + 1 + (j - l - 1));
+                        }
+                    }
+                }
+
+                d[i][j] = best;
+            }
+        }
+
+        d[u.len()][v.len()]
+    }
+
+    /// Returns the least amount of insertions, deletions, substitutions and
+    /// transpositions of adjacent residues that turns `u` into `v`, found by a
+    /// breadth-first search over the edited sequences.
+    fn damerau_brute_force(u: &[u8], v: &[u8]) -> usize {
+        let mut seen = std::collections::HashSet::from([u.to_vec()]);
+        let mut frontier = vec![u.to_vec()];
+
+        for distance in 0.. {
+            if frontier.iter().any(|w| w == v) {
+                return distance;
+            }
+
+            let mut next = Vec::new();
+
+            for w in frontier {
+                let mut edits = Vec::new();
+
+                for i in 0..=w.len() {
+                    for &c in v {
+                        edits.push([&w[..i], &[c], &w[i..]].concat());
+
+                        if i < w.len() {
+                            edits.push([&w[..i], &[c], &w[i + 1..]].concat());
+                        }
+                    }
+
+                    if i < w.len() {
+                        edits.push([&w[..i], &w[i + 1..]].concat());
+                    }
+
+                    if i + 1 < w.len() {
+                        let mut swapped = w.clone();
+                        swapped.swap(i, i + 1);
+                        edits.push(swapped);
+                    }
+                }
+
+                for edit in edits {
+                    if seen.insert(edit.clone()) {
+                        next.push(edit);
+                    }
+                }
+            }
+
+            frontier = next;
+        }
+
+        unreachable!()
+    }
+
+    #[test]
+    fn myers_distance_test() {
+        assert_eq!(myers_distance("KITTEN", "SITTING"), 3);
+        assert_eq!(myers_distance("", "ACGT"), 4);
+        assert_eq!(myers_distance("ACGT", ""), 4);
+
+        // The patterns longer than 64 residues are split in blocks.
+        for len in [1, 20, 63, 64, 65, 128, 150] {
+            let u = random_sequence(len, len as u64);
+            let v = random_sequence(len + 17, len as u64 + 1);
+
+            assert_eq!(myers_distance(&u, &v), levenshtein(&u, &v));
+        }
+    }
+
+    #[test]
+    fn myers_search_test() {
+        let occurrences = myers_search("GATTACA", "CCGATTACATTGATCACAGG", 0);
+
+        assert_eq!(occurrences, vec![(9, 0)]);
+
+        for len in [5, 64, 70] {
+            let pattern = random_sequence(len, 3);
+            let text = format!("{}{}{}", random_sequence(15, 4), &pattern[1..], random_sequence(15, 5));
+            let k = len / 5;
+
+            let occurrences = myers_search(&pattern, &text, k);
+
+            // Each end is checked against the least distance to any substring
+            // of the text ending there.
+            for end in 1..=text.len() {
+                let distance = (0..end)
+                    .map(|start| levenshtein(&pattern, &text[start..end]))
+                    .min()
+                    .unwrap();
+
+                let expected = (distance <= k).then_some((end, distance));
+
+                assert_eq!(occurrences.iter().find(|&&(e, _)| e == end).copied(), expected);
+            }
+
+            // The planted occurrence lacks the first residue of the pattern.
+            assert!(occurrences.iter().any(|&(end, d)| end == 15 + len - 1 && d <= 1));
+        }
+    }
+
+    #[test]
+    fn hamming_test() {
+        assert_eq!(hamming("ATGAT", "TTAGT"), Ok(3));
+        assert_eq!(hamming("", ""), Ok(0));
+        assert_eq!(
+            hamming("ACGT", "ACG"),
+            Err(HammingError::LengthMismatch { u_len: 4, v_len: 3 })
+        );
+
+        // The lengths cover partial words and exact multiples of 32.
+        for len in [1, 31, 32, 33, 64, 1000] {
+            let u = random_sequence(len, len as u64);
+            let v = random_sequence(len, len as u64 + 1);
+
+            let (pu, pv) = (PackedDna::new(&u).unwrap(), PackedDna::new(&v).unwrap());
+
+            assert_eq!(pu.len(), len);
+            assert_eq!(pu.to_string(), u);
+            assert_eq!(pu.hamming(&pv), hamming(&u, &v));
+        }
+
+        let (pu, pv) = (PackedDna::new("ACGT").unwrap(), PackedDna::new("ACG").unwrap());
+
+        assert_eq!(pu.hamming(&pv), Err(HammingError::LengthMismatch { u_len: 4, v_len: 3 }));
+        assert_eq!(
+            HammingError::LengthMismatch { u_len: 4, v_len: 3 }.to_string(),
+            "Sequences must have the same length, but have 4 and 3"
+        );
+        assert!(PackedDna::new("ACGU").is_err());
+    }
+
+    /// Returns the standard output of running the binary with the arguments
+    /// `args`, or its standard error if it has failed.
+    fn run_cli(args: &[&str]) -> Result<String, String> {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_bioinformatics"))
+            .args(args)
+            .output()
+            .unwrap();
+
+        if output.status.success() {
+            Ok(String::from_utf8(output.stdout).unwrap())
+        } else {
+            Err(String::from_utf8(output.stderr).unwrap())
+        }
+    }
+
+    #[test]
+    fn cli_non_ascii_test() {
+        // The sequences are checked as the FASTA records, hence only ASCII residues are valid.
+        let err = run_cli(&["local", "GAéCT", "AéC", "--format", "json"]).unwrap_err();
+
+        assert!(err.starts_with("Residue `é` of sequence `GAéCT` is not valid"));
+    }
+
+    /// Returns the path of a new temporary file named `name` with the `content`.
+    fn temp_file(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("bioinformatics-{}-{}", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn cli_input_test() {
+        let path = temp_file("input.fa", ">u\nACGT\n");
+
+        assert_eq!(run_cli(&["hamming", &path, "ACGA"]), Ok(String::from("1\n")));
+        assert_eq!(run_cli(&["hamming", "ACG-", "ACG*"]), Ok(String::from("1\n")));
+
+        assert!(run_cli(&["global", "ACGT", "nosuchfile.fa"]).is_err());
+        assert!(run_cli(&["global", "ACGT", "./nosuchfile"]).is_err());
+        assert!(run_cli(&["global", "ACGT", "AC GT"]).is_err());
+        assert!(run_cli(&["global", "ACGT", "AC1T"]).is_err());
+
+        // A sequence given directly is converted to uppercase, as the FASTA records.
+        let lower = temp_file("lower.fa", ">u\nacgt\n");
+
+        assert_eq!(run_cli(&["global", "acgt", "ACGT"]), run_cli(&["global", &lower, "ACGT"]));
+        assert_eq!(
+            run_cli(&["global", "acgt", "ACGT", "--format", "score"]),
+            Ok(String::from("u\tv\t20\t4\t4\n"))
+        );
+
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(lower).unwrap();
+    }
+
+    #[test]
+    fn cli_args_test() {
+        assert!(run_cli(&["help"]).unwrap().starts_with("Usage:"));
+        assert!(run_cli(&["global", "--help"]).unwrap().starts_with("Usage:"));
+
+        assert_eq!(
+            run_cli(&["global", "--match", "1", "--mismatch", "-1", "--gap", "-1", "--format", "score", "AAT", "AT"]),
+            Ok(String::from("u\tv\t1\t3\t2\n"))
+        );
+        assert_eq!(
+            run_cli(&["global", "--mode", "semi-global", "--format", "score", "ACGTTTT", "CGT"]),
+            Ok(String::from("u\tv\t15\t4\t3\n"))
+        );
+        assert_eq!(
+            run_cli(&["global", "--gap-open", "-10", "--gap-extend", "-1", "--format", "score", "ACGTTTACGT", "ACGTACGT"]),
+            Ok(String::from("u\tv\t29\t10\t8\n"))
+        );
+        assert_eq!(
+            run_cli(&["global", "--matrix", "BLOSUM62", "--gap", "-8", "--format", "score", "HEAGAWGHEE", "PAWHEAE"]),
+            Ok(String::from("u\tv\t-8\t10\t7\n"))
+        );
+
+        assert!(run_cli(&["global", "ACGT"]).is_err());
+        assert!(run_cli(&["global", "ACGT", "ACGT", "ACGT"]).is_err());
+        assert!(run_cli(&["global", "--all-vs-all", "ACGT", "ACGT"]).is_err());
+        assert!(run_cli(&["global", "--match", "x", "ACGT", "ACGT"]).is_err());
+        assert!(run_cli(&["global", "--match", "128", "ACGT", "ACGT"]).is_err());
+        assert!(run_cli(&["global", "ACGT", "ACGT", "--gap"]).is_err());
+        assert!(run_cli(&["global", "--foo", "1", "ACGT", "ACGT"]).is_err());
+        assert!(run_cli(&["global", "--mode", "glocal", "ACGT", "ACGT"]).is_err());
+        assert!(run_cli(&["global", "--matrix", "BLOSUM0", "ACGT", "ACGT"]).is_err());
+        assert!(run_cli(&["global", "--gap-extend", "-1", "ACGT", "ACGT"]).is_err());
+        assert!(run_cli(&["global", "--gap-open", "-5", "--mode", "overlap", "ACGT", "ACGT"]).is_err());
+        assert!(run_cli(&["local", "ACGT", "ACGT", "--mode", "fitting"])
+            .unwrap_err()
+            .starts_with("Option `--mode` is not supported with command `local`"));
+        assert!(run_cli(&["lcs", "--mode", "global", "ACGT", "ACGT"]).is_err());
+        assert_eq!(run_cli(&["global", "--threads", "2", "ACGT", "ACGT"]).is_ok(), cfg!(feature = "parallel"));
+        assert!(run_cli(&["hamming", "ACGT", "ACG"]).unwrap_err().contains("but have 4 and 3"));
+    }
+
+    #[test]
+    fn cli_format_test() {
+        // The identifier contains a quote, a backslash and a control character.
+        let path = temp_file("format.fa", ">q\"1\\x\u{1} query\nGGACCATTATGG\n");
+
+        assert_eq!(
+            run_cli(&["local", &path, "CCAATATG", "--format", "json"]),
+            Ok(String::from(
+                "{\"u_id\":\"q\\\"1\\\\x\\u0001\",\"v_id\":\"v\",\"score\":32,\
+                 \"aligned_u\":\"CCATTATG\",\"aligned_v\":\"CCAATATG\",\"u_start\":3,\"u_end\":11,\
+                 \"v_start\":0,\"v_end\":8,\"identity\":87.50,\"cigar\":\"3=1X4=\"}\n"
+            ))
+        );
+
+        // The tabular coordinates are one-based and inclusive.
+        assert_eq!(
+            run_cli(&["local", &path, "CCAATATG", "--format", "tsv"]),
+            Ok(String::from("q\"1\\x\u{1}\tv\t87.50\t8\t1\t0\t4\t11\t1\t8\t32\n"))
+        );
+        assert_eq!(
+            run_cli(&["local", &path, "CCAATATG", "--format", "score"]),
+            Ok(String::from("q\"1\\x\u{1}\tv\t32\t11\t8\n"))
+        );
+        assert_eq!(
+            run_cli(&["local", &path, "CCAATATG", "--format", "text"]),
+            Ok(String::from("Score: 32\nCCATTATG\nCCAATATG\n"))
+        );
+
+        assert_eq!(
+            run_cli(&["lcs", "ACGTA", "CGA", "--format", "json"]),
+            Ok(String::from(
+                "{\"u_id\":\"u\",\"v_id\":\"v\",\"score\":3,\"aligned_u\":\"ACGTA\",\"aligned_v\":\"-CG-A\",\
+                 \"u_start\":0,\"u_end\":5,\"v_start\":0,\"v_end\":3,\"identity\":60.00,\"cigar\":\"1D2=1D1=\"}\n"
+            ))
+        );
+        assert_eq!(run_cli(&["lcs", "ACGTA", "CGA"]), Ok(String::from("CGA\n")));
+        assert_eq!(
+            run_cli(&["hamming", "ACGT", "ACCA", "--format", "tsv"]),
+            Ok(String::from("u\tv\t50.00\t4\t2\t0\t1\t4\t1\t4\t2\n"))
+        );
+
+        assert!(run_cli(&["global", "ACGT", "ACGT", "--format", "xml"]).is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn cli_batch_test() {
+        let queries = temp_file("queries.fa", ">q1\nACGT\n>q2\nACGA\n");
+        let database = temp_file("database.fa", ">d1\nACGT\n>d2\nTCGA\n>d3\nAAAA\n");
+
+        let rows = run_cli(&["hamming", "--batch", &queries, &database, "--format", "score"]).unwrap();
+
+        assert_eq!(
+            rows,
+            "q1\td1\t0\t4\t4\nq1\td2\t2\t4\t4\nq1\td3\t3\t4\t4\n\
+             q2\td1\t1\t4\t4\nq2\td2\t1\t4\t4\nq2\td3\t2\t4\t4\n"
+        );
+        assert_eq!(
+            run_cli(&["hamming", "--batch", &queries, &database]).unwrap().lines().count(),
+            6
+        );
+        assert!(run_cli(&["hamming", "--batch", &queries, &database, "--format", "text"])
+            .unwrap()
+            .starts_with("# q1\td1\n0\n# q1\td2\n2\n"));
+
+        // The pairs span more than one chunk of `1024` pairs.
+        let records = (0..47).map(|k| format!(">r{}\n{}\n", k, random_sequence(4, k))).collect::<String>();
+        let records = temp_file("records.fa", &records);
+
+        let rows = run_cli(&["hamming", "--all-vs-all", &records, "--format", "score"]).unwrap();
+        let pairs = rows
+            .lines()
+            .map(|row| {
+                let ids = row.split('\t').take(2).collect::<Vec<_>>();
+                (ids[0].to_string(), ids[1].to_string())
+            })
+            .collect::<Vec<_>>();
+
+        let expected = (0..47)
+            .flat_map(|i| (i + 1..47).map(move |j| (format!("r{}", i), format!("r{}", j))))
+            .collect::<Vec<_>>();
+
+        assert_eq!(pairs, expected);
+
+        if cfg!(feature = "parallel") {
+            assert_eq!(
+                run_cli(&["hamming", "--all-vs-all", &records, "--format", "score", "--threads", "4"]),
+                Ok(rows)
+            );
+        }
+
+        // A pair that cannot be aligned is skipped and reported.
+        let mixed = temp_file("mixed.fa", ">a\nACGT\n>b\nACG\n>c\nACGA\n");
+        let err = run_cli(&["hamming", "--all-vs-all", &mixed]).unwrap_err();
+
+        assert!(err.starts_with("a\tb: "));
+        assert!(err.contains("2 pairs could not be aligned"));
+
+        for path in [queries, database, records, mixed] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    /// Returns the global alignment matrices of `u` and `v` stored as a `Vec` of
+    /// rows, as [`Matrix`] did before its cells were stored in a single buffer,
+    /// so that the benchmarks measure the gain of the contiguous layout.
+    fn align_global_nested(
+        u: &[u8],
+        v: &[u8],
+        opt: &global::Options,
+    ) -> (Vec<Vec<i32>>, Vec<Vec<Traceback>>) {
+        let gap = opt.gap as i32;
+
+        let mut s = vec![vec![0i32; v.len() + 1]; u.len() + 1];
+        let mut b = vec![vec![Traceback::NONE; v.len() + 1]; u.len() + 1];
+
+        for i in 1..=u.len() {
+            s[i][0] = (i as i32) * gap;
+            b[i][0] = Traceback::UP;
+        }
+
+        for j in 1..=v.len() {
+            s[0][j] = (j as i32) * gap;
+            b[0][j] = Traceback::LEFT;
+        }
+
+        for i in 1..=u.len() {
+            for j in 1..=v.len() {
+                let diagonal = s[i - 1][j - 1] + opt.score(u[i - 1], v[j - 1]);
+                let upper = s[i - 1][j] + gap;
+                let left = s[i][j - 1] + gap;
+
+                let best = diagonal.max(upper).max(left);
+
+                s[i][j] = best;
+                b[i][j] = Traceback::from_scores(best, diagonal, upper, left);
+            }
+        }
+
+        (s, b)
+    }
+
+    #[test]
+    fn align_global_nested_test() {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let u = random_sequence(50, 1);
+        let v = random_sequence(40, 2);
+
+        let (s, b) = global::align_global(&u, &v, &opt);
+        let (ns, nb) = align_global_nested(u.as_bytes(), v.as_bytes(), &opt);
+
+        for i in 0..s.row() {
+            assert_eq!(s[i], ns[i][..]);
+            assert_eq!(b[i], nb[i][..]);
+        }
+    }
+
+    #[bench]
+    fn align_global_1000_bench(b: &mut Bencher) {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let u = random_sequence(1000, 1);
+        let v = random_sequence(1000, 2);
+
+        b.iter(|| global::align_global(&u, &v, &opt));
+    }
+
+    #[bench]
+    fn align_global_nested_1000_bench(b: &mut Bencher) {
+        let opt = global::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let u = random_sequence(1000, 1);
+        let v = random_sequence(1000, 2);
+
+        b.iter(|| align_global_nested(u.as_bytes(), v.as_bytes(), &opt));
+    }
+
+    #[bench]
+    fn align_local_1000_bench(b: &mut Bencher) {
+        let opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let u = random_sequence(1000, 1);
+        let v = random_sequence(1000, 2);
+
+        b.iter(|| local::align_local(&u, &v, &opt));
+    }
+
+    #[bench]
+    fn align_local_striped_1000_bench(b: &mut Bencher) {
+        let opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let u = random_sequence(1000, 1);
+        let v = random_sequence(1000, 2);
+        let profile = Profile::new(&u, &opt);
+
+        b.iter(|| profile.score(&v));
+    }
+
+    #[bench]
+    fn score_local_1000_bench(b: &mut Bencher) {
+        let opt = local::Options {
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            substitution: None,
+        };
+
+        let u = random_sequence(1000, 1);
+        let v = random_sequence(1000, 2);
+
+        b.iter(|| local::score_local(&u, &v, &opt));
+    }
+
+    #[bench]
+    fn levenshtein_1000_bench(b: &mut Bencher) {
+        let u = random_sequence(1000, 1);
+        let v = random_sequence(1000, 2);
+
+        b.iter(|| levenshtein(&u, &v));
+    }
+
+    #[bench]
+    fn myers_distance_1000_bench(b: &mut Bencher) {
+        let u = random_sequence(1000, 1);
+        let v = random_sequence(1000, 2);
+
+        b.iter(|| myers_distance(&u, &v));
+    }
+
+    #[bench]
+    fn myers_search_bench(b: &mut Bencher) {
+        let pattern = random_sequence(64, 1);
+        let text = random_sequence(100_000, 2);
+
+        b.iter(|| myers_search(&pattern, &text, 8));
+    }
+
+    #[bench]
+    fn hamming_1m_bench(b: &mut Bencher) {
+        let u = random_sequence(1_000_000, 1);
+        let v = random_sequence(1_000_000, 2);
+
+        b.iter(|| hamming(&u, &v));
+    }
+
+    #[bench]
+    fn hamming_packed_1m_bench(b: &mut Bencher) {
+        let u = PackedDna::new(random_sequence(1_000_000, 1)).unwrap();
+        let v = PackedDna::new(random_sequence(1_000_000, 2)).unwrap();
+
+        b.iter(|| u.hamming(&v));
+    }
+
+    #[bench]
+    fn lcs_1000_bench(b: &mut Bencher) {
+        let u = random_sequence(1000, 1);
+        let v = random_sequence(1000, 2);
+
+        b.iter(|| lcs(&u, &v));
+    }
 }
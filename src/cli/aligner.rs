@@ -0,0 +1,169 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::cli::{Args, Command};
+use bioinformatics::sequencing::alignment::{Alignment, Operation};
+use bioinformatics::sequencing::lcs::{lcs, traceback_lcs};
+use bioinformatics::sequencing::{global, hamming, local};
+
+/// The algorithm run by a command, with its scoring options.
+///
+/// It is built once from the command-line arguments, so that the substitution
+/// matrix is loaded only once even if many pairs of sequences are aligned.
+pub enum Aligner {
+    Lcs,
+    Hamming,
+    Global(global::Options, global::Mode),
+    GlobalAffine(global::AffineOptions),
+    Local(local::Options),
+    LocalAffine(local::AffineOptions),
+}
+
+impl Aligner {
+    /// Constructs the [`Aligner`] of the command in `args`.
+    ///
+    /// Further, it is returned a [`Result<Aligner, String>`] that will contain
+    /// an error if the substitution matrix cannot be loaded.
+    pub fn new(args: &Args) -> Result<Self, String> {
+        let aligner = match args.command {
+            Command::Lcs => Aligner::Lcs,
+            Command::Hamming => Aligner::Hamming,
+            Command::Global => match args.gap_open {
+                Some(gap_open) => Aligner::GlobalAffine(global::AffineOptions {
+                    match_: args.match_,
+                    mismatch: args.mismatch,
+                    gap_open,
+                    gap_extend: args.gap_extend.unwrap_or(gap_open),
+                    substitution: args.substitution()?,
+                }),
+                None => Aligner::Global(
+                    global::Options {
+                        match_: args.match_,
+                        mismatch: args.mismatch,
+                        gap: args.gap,
+                        substitution: args.substitution()?,
+                    },
+                    args.mode.unwrap_or(global::Mode::Global),
+                ),
+            },
+            Command::Local => match args.gap_open {
+                Some(gap_open) => Aligner::LocalAffine(local::AffineOptions {
+                    match_: args.match_,
+                    mismatch: args.mismatch,
+                    gap_open,
+                    gap_extend: args.gap_extend.unwrap_or(gap_open),
+                    substitution: args.substitution()?,
+                }),
+                None => Aligner::Local(local::Options {
+                    match_: args.match_,
+                    mismatch: args.mismatch,
+                    gap: args.gap,
+                    substitution: args.substitution()?,
+                }),
+            },
+            Command::Help => return Err(String::from("Command `help` aligns no sequence")),
+        };
+
+        Ok(aligner)
+    }
+
+    /// Returns the alignment between `u` and `v`.
+    ///
+    /// The score of the alignment is the length of the longest common subsequence
+    /// for [`Aligner::Lcs`] and the distance for [`Aligner::Hamming`].
+    ///
+    /// Further, it is returned a [`Result<Alignment, String>`] that will contain
+    /// an error if the Hamming distance is computed for sequences of different
+    /// lengths.
+    pub fn align(&self, u: &[u8], v: &[u8]) -> Result<Alignment, String> {
+        let alignment = match self {
+            Aligner::Lcs => {
+                let (s, b) = lcs(u, v);
+                traceback_lcs(&s, &b, u, v)
+            }
+            Aligner::Hamming => {
+                let distance = hamming(u, v).map_err(|err| err.to_string())?;
+
+                let operations = u
+                    .iter()
+                    .zip(v.iter())
+                    .map(|(a, b)| {
+                        if a == b {
+                            Operation::Match
+                        } else {
+                            Operation::Mismatch
+                        }
+                    })
+                    .collect();
+
+                Alignment::new(u, v, 0, 0, operations, distance as i32)
+            }
+            Aligner::Global(opt, mode) => {
+                let (s, b) = global::align_global_mode(u, v, opt, *mode);
+                global::traceback_global_mode(&s, &b, u, v, *mode)
+            }
+            Aligner::GlobalAffine(opt) => {
+                let (s, tb) = global::align_global_affine(u, v, opt);
+                global::traceback_global_affine(&s, &tb, u, v)
+            }
+            Aligner::Local(opt) => {
+                let (s, b) = local::align_local(u, v, opt);
+                local::traceback_local(&s, &b, u, v)
+            }
+            Aligner::LocalAffine(opt) => {
+                let (s, tb) = local::align_local_affine(u, v, opt);
+                local::traceback_local_affine(&s, &tb, u, v)
+            }
+        };
+
+        Ok(alignment)
+    }
+
+    /// Returns the score of the alignment between `u` and `v`, together with the
+    /// end coordinates of the alignment in `u` and `v`.
+    ///
+    /// The global and local alignments are scored in linear memory, without
+    /// building the alignment. The score is the same as that of [`Aligner::align`].
+    ///
+    /// Further, it is returned a [`Result<(i32, usize, usize), String>`] that will
+    /// contain an error if the Hamming distance is computed for sequences of
+    /// different lengths.
+    pub fn score(&self, u: &[u8], v: &[u8]) -> Result<(i32, usize, usize), String> {
+        let score = match self {
+            Aligner::Lcs => {
+                let alignment = self.align(u, v)?;
+                (alignment.score, alignment.u_end, alignment.v_end)
+            }
+            Aligner::Hamming => {
+                let distance = hamming(u, v).map_err(|err| err.to_string())?;
+                (distance as i32, u.len(), v.len())
+            }
+            Aligner::Global(opt, mode) => global::score_global_mode(u, v, opt, *mode),
+            Aligner::GlobalAffine(opt) => global::score_global_affine(u, v, opt),
+            Aligner::Local(opt) => local::score_local(u, v, opt),
+            Aligner::LocalAffine(opt) => local::score_local_affine(u, v, opt),
+        };
+
+        Ok(score)
+    }
+}
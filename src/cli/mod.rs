@@ -0,0 +1,614 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use aligner::Aligner;
+use bioinformatics::formats::fasta;
+use bioinformatics::sequencing::alignment::Alignment;
+use bioinformatics::sequencing::global;
+#[cfg(feature = "parallel")]
+use bioinformatics::sequencing::parallel::map_pairs;
+use bioinformatics::sequencing::substitution::SubstitutionMatrix;
+use output::{Format, Report, ScoreReport};
+use std::io::Write;
+use std::path::Path;
+
+pub mod aligner;
+pub mod output;
+
+/// The usage message of the non-interactive mode.
+pub const USAGE: &str = "\
+Usage: bioinformatics [U V]
+       bioinformatics <COMMAND> [OPTIONS] <U> <V>
+       bioinformatics <COMMAND> [OPTIONS] --batch <QUERIES> <DATABASE>
+       bioinformatics <COMMAND> [OPTIONS] --all-vs-all <RECORDS>
+
+Without a command, the sequences are read from the arguments or the standard
+input and the algorithm is chosen from an interactive menu.
+
+Each sequence <U> and <V> is either the path of a FASTA file, whose first record
+is used, or the sequence itself, made of ASCII letters, `*` and `-`, which is
+converted to uppercase as the FASTA records. An argument that contains `/`, `\\`
+or `.` is always read as a file.
+
+In the batch modes, every record of <QUERIES> is aligned against every record of
+<DATABASE>, or every pair of distinct records of <RECORDS> is aligned, writing
+one result per pair as soon as it is computed.
+
+Commands:
+  lcs        Longest common subsequence
+  global     Needleman-Wunsch global alignment
+  local      Smith-Waterman local alignment
+  hamming    Hamming distance
+  help       Print this message
+
+Options:
+  --match <N>         Match point [default: 5]
+  --mismatch <N>      Mismatch point [default: -3]
+  --gap <N>           Gap point [default: -4]
+  --gap-open <N>      Gap open point, enabling affine gaps
+  --gap-extend <N>    Gap extend point [default: the gap open point]
+  --matrix <NAME>     Substitution matrix, either a built-in one (e.g. BLOSUM62)
+                      or the path of a matrix file
+  --mode <MODE>       Alignment mode of the global command: global,
+                      semi-global, fitting or overlap [default: global]
+  --format <FORMAT>   Output format: text, json (one object per line), tsv
+                      (BLAST-style rows) or score (only the score and the end
+                      coordinates, computed in linear memory) [default: text,
+                      or tsv in batch modes]
+  --batch             Align every query against every database record
+  --all-vs-all        Align every pair of distinct records
+  --threads <N>       Threads used to align the pairs in the batch modes, or 0
+                      to use every core; it requires the `parallel` feature
+                      [default: 1]
+
+The tsv columns are u_id, v_id, identity, length, mismatches, gap_opens,
+u_start, u_end, v_start, v_end and score, with one-based inclusive coordinates.
+The json objects contain the score, the aligned rows, the zero-based
+end-exclusive coordinates, the identity and the extended CIGAR string, in which
+<U> is the reference. The score is the length of the subsequence for lcs and
+the distance for hamming. The score rows contain u_id, v_id, score, u_end and
+v_end.";
+
+/// A subcommand of the binary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    Lcs,
+    Global,
+    Local,
+    Hamming,
+    Help,
+}
+
+impl Command {
+    /// Returns the command named `name`, if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lcs" => Some(Command::Lcs),
+            "global" => Some(Command::Global),
+            "local" => Some(Command::Local),
+            "hamming" => Some(Command::Hamming),
+            "help" | "-h" | "--help" => Some(Command::Help),
+            _ => None,
+        }
+    }
+}
+
+/// The batch mode of the non-interactive mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Batch {
+    /// A single pair of sequences is aligned.
+    Single,
+
+    /// Every record of the first input is aligned against every record of
+    /// the second input.
+    QueryDatabase,
+
+    /// Every pair of distinct records of the single input is aligned.
+    AllVsAll,
+}
+
+/// The parsed command-line arguments of the non-interactive mode.
+pub struct Args {
+    pub command: Command,
+    pub match_: i8,
+    pub mismatch: i8,
+    pub gap: i8,
+    pub gap_open: Option<i8>,
+    pub gap_extend: Option<i8>,
+    pub matrix: Option<String>,
+    pub mode: Option<global::Mode>,
+    pub format: Option<Format>,
+    pub batch: Batch,
+    pub threads: usize,
+    pub inputs: Vec<String>,
+}
+
+impl Args {
+    /// Parses the command-line arguments `args`, excluding the program name.
+    ///
+    /// Further, it is returned a [`Result<Args, String>`] that will contain an
+    /// error if the command is unknown, an option is unknown, has an invalid
+    /// value or is not supported by the command, or the amount of sequences is
+    /// not two.
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let command = match args.first() {
+            Some(name) => match Command::from_name(name) {
+                Some(command) => command,
+                None => return Err(format!("Command `{}` is not supported", name)),
+            },
+            None => return Err(String::from("No command was specified")),
+        };
+
+        let mut parsed = Self {
+            command,
+            match_: 5,
+            mismatch: -3,
+            gap: -4,
+            gap_open: None,
+            gap_extend: None,
+            matrix: None,
+            mode: None,
+            format: None,
+            batch: Batch::Single,
+            threads: 1,
+            inputs: Vec::new(),
+        };
+
+        if command == Command::Help {
+            return Ok(parsed);
+        }
+
+        let mut tokens = args[1..].iter();
+
+        while let Some(token) = tokens.next() {
+            if !token.starts_with("--") {
+                parsed.inputs.push(token.clone());
+                continue;
+            }
+
+            match token.as_str() {
+                "--help" => {
+                    parsed.command = Command::Help;
+                    return Ok(parsed);
+                }
+                "--batch" => {
+                    parsed.batch = Batch::QueryDatabase;
+                    continue;
+                }
+                "--all-vs-all" => {
+                    parsed.batch = Batch::AllVsAll;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let value = match tokens.next() {
+                Some(value) => value.as_str(),
+                None => return Err(format!("Option `{}` requires a value", token)),
+            };
+
+            match token.as_str() {
+                "--match" => parsed.match_ = parse_point(token, value)?,
+                "--mismatch" => parsed.mismatch = parse_point(token, value)?,
+                "--gap" => parsed.gap = parse_point(token, value)?,
+                "--gap-open" => parsed.gap_open = Some(parse_point(token, value)?),
+                "--gap-extend" => parsed.gap_extend = Some(parse_point(token, value)?),
+                "--matrix" => parsed.matrix = Some(String::from(value)),
+                "--mode" => parsed.mode = Some(parse_mode(value)?),
+                "--format" => parsed.format = Some(parse_format(value)?),
+                "--threads" => parsed.threads = parse_threads(value)?,
+                _ => return Err(format!("Option `{}` is not supported", token)),
+            }
+        }
+
+        let expected = match parsed.batch {
+            Batch::AllVsAll => 1,
+            _ => 2,
+        };
+
+        if parsed.inputs.len() != expected {
+            return Err(format!(
+                "Expected {} input(s), but {} were specified",
+                expected,
+                parsed.inputs.len()
+            ));
+        }
+
+        if parsed.gap_open.is_none() && parsed.gap_extend.is_some() {
+            return Err(String::from("Option `--gap-extend` requires `--gap-open`"));
+        }
+
+        if parsed.mode.is_some() && command != Command::Global {
+            return Err(format!(
+                "Option `--mode` is not supported with command `{}`",
+                args[0]
+            ));
+        }
+
+        if parsed.gap_open.is_some() && parsed.mode.is_some_and(|mode| mode != global::Mode::Global)
+        {
+            return Err(String::from(
+                "Option `--mode` is not supported with affine gaps",
+            ));
+        }
+
+        Ok(parsed)
+    }
+
+    /// Returns the output format, that defaults to tabular rows in the batch
+    /// modes and to text otherwise.
+    pub fn format(&self) -> Format {
+        match (self.format, self.batch) {
+            (Some(format), _) => format,
+            (None, Batch::Single) => Format::Text,
+            (None, _) => Format::Tsv,
+        }
+    }
+
+    /// Returns the substitution matrix specified by `--matrix`, if any.
+    pub fn substitution(&self) -> Result<Option<SubstitutionMatrix>, String> {
+        let name = match &self.matrix {
+            Some(name) => name,
+            None => return Ok(None),
+        };
+
+        if let Some(matrix) = SubstitutionMatrix::builtin(name) {
+            return Ok(Some(matrix));
+        }
+
+        match std::fs::read_to_string(name) {
+            Ok(text) => SubstitutionMatrix::parse(&text)
+                .map(Some)
+                .map_err(|err| format!("{}: {}", name, err)),
+            Err(err) => Err(format!("{}: {}", name, err)),
+        }
+    }
+}
+
+/// Returns the score point given to the option `option`.
+fn parse_point(option: &str, value: &str) -> Result<i8, String> {
+    value.parse::<i8>().map_err(|_| {
+        format!(
+            "Option `{}` expects an integer, but `{}` was given",
+            option, value
+        )
+    })
+}
+
+/// Returns the global alignment mode named `name`.
+fn parse_mode(name: &str) -> Result<global::Mode, String> {
+    match name {
+        "global" => Ok(global::Mode::Global),
+        "semi-global" => Ok(global::Mode::SemiGlobal),
+        "fitting" => Ok(global::Mode::Fitting),
+        "overlap" => Ok(global::Mode::Overlap),
+        _ => Err(format!("Mode `{}` is not supported", name)),
+    }
+}
+
+/// Returns the amount of threads given to the option `--threads`.
+fn parse_threads(value: &str) -> Result<usize, String> {
+    let threads = value.parse::<usize>().map_err(|_| {
+        format!(
+            "Option `--threads` expects a non-negative integer, but `{}` was given",
+            value
+        )
+    })?;
+
+    if cfg!(not(feature = "parallel")) && threads != 1 {
+        return Err(String::from(
+            "Option `--threads` requires the binary to be built with the `parallel` feature",
+        ));
+    }
+
+    Ok(threads)
+}
+
+/// Returns the output format named `name`.
+fn parse_format(name: &str) -> Result<Format, String> {
+    match Format::from_name(name) {
+        Some(format) => Ok(format),
+        None => Err(format!("Format `{}` is not supported", name)),
+    }
+}
+
+/// Returns the record specified by the command-line argument `arg`.
+///
+/// If `arg` is a file, then its first FASTA record is returned, otherwise `arg`
+/// is the sequence itself, identified by `id`, as in [`read_records`].
+pub fn read_record(arg: &str, id: &str) -> Result<fasta::Record, String> {
+    match read_records(arg, id)?.next() {
+        Some(record) => record,
+        None => Err(format!("{}: File has no FASTA record", arg)),
+    }
+}
+
+/// Returns the sequence specified by the command-line argument `arg`.
+///
+/// If `arg` is a file, then the sequence of its first FASTA record is returned,
+/// otherwise `arg` is the sequence itself, as in [`read_records`].
+pub fn read_sequence(arg: &str) -> Result<String, String> {
+    read_record(arg, "").map(|record| record.sequence)
+}
+
+/// Returns an iterator over the records specified by the command-line argument
+/// `arg`, that reads them one at a time.
+///
+/// If `arg` is an existing file or it looks like a path, that is, it contains
+/// `/`, `\` or `.`, then its FASTA records are returned, otherwise `arg` is the
+/// sequence of a single record identified by `id`, whose residues are converted
+/// to uppercase as in the [`fasta::Reader`].
+///
+/// Further, it is returned a [`Result`] that will contain an error if the file
+/// cannot be opened, or the sequence contains a residue other than an ASCII
+/// letter, `*` or `-`.
+pub fn read_records(
+    arg: &str,
+    id: &str,
+) -> Result<Box<dyn Iterator<Item = Result<fasta::Record, String>>>, String> {
+    if !Path::new(arg).is_file() && !arg.contains(['/', '\\', '.']) {
+        if let Some(c) = arg
+            .chars()
+            .find(|&c| !c.is_ascii_alphabetic() && c != '*' && c != '-')
+        {
+            return Err(format!(
+                "Residue `{}` of sequence `{}` is not valid",
+                c, arg
+            ));
+        }
+
+        let record = fasta::Record {
+            id: String::from(id),
+            description: None,
+            sequence: arg.to_ascii_uppercase(),
+        };
+
+        return Ok(Box::new(std::iter::once(Ok(record))));
+    }
+
+    let path = String::from(arg);
+    let reader = fasta::Reader::from_file(arg)?;
+
+    Ok(Box::new(reader.map(move |record| {
+        record.map_err(|err| format!("{}: {}", path, err))
+    })))
+}
+
+/// The amount of pairs aligned at a time in the batch modes.
+const CHUNK: usize = 1024;
+
+/// Runs the non-interactive mode with the parsed `args`, printing the
+/// results to the standard output.
+pub fn run(args: &Args) -> Result<(), String> {
+    if args.command == Command::Help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    let aligner = Aligner::new(args)?;
+
+    let mut printer = Printer {
+        args,
+        out: std::io::stdout().lock(),
+        failures: 0,
+    };
+
+    match args.batch {
+        Batch::Single => {
+            let u = read_record(&args.inputs[0], "u")?;
+            let v = read_record(&args.inputs[1], "v")?;
+
+            printer.print_all(&aligner, &[(&u, &v)])?;
+        }
+        Batch::QueryDatabase => {
+            // The database is kept in memory, while the queries are streamed
+            // in chunks, so that the pairs of a chunk are aligned in parallel.
+            let database = read_records(&args.inputs[1], "v")?.collect::<Result<Vec<_>, _>>()?;
+            let mut queries = read_records(&args.inputs[0], "u")?.peekable();
+
+            while queries.peek().is_some() {
+                let chunk = queries
+                    .by_ref()
+                    .take(CHUNK.div_ceil(database.len().max(1)))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let pairs = chunk
+                    .iter()
+                    .flat_map(|query| database.iter().map(move |record| (query, record)))
+                    .collect::<Vec<_>>();
+
+                printer.print_all(&aligner, &pairs)?;
+            }
+        }
+        Batch::AllVsAll => {
+            let records = read_records(&args.inputs[0], "u")?.collect::<Result<Vec<_>, _>>()?;
+
+            // The pairs are generated lazily, so that only a chunk of them is
+            // kept in memory at a time.
+            let mut pairs = records
+                .iter()
+                .enumerate()
+                .flat_map(|(i, u)| records[i + 1..].iter().map(move |v| (u, v)))
+                .peekable();
+
+            while pairs.peek().is_some() {
+                let chunk = pairs.by_ref().take(CHUNK).collect::<Vec<_>>();
+
+                printer.print_all(&aligner, &chunk)?;
+            }
+        }
+    }
+
+    printer.out.flush().map_err(|err| err.to_string())?;
+
+    if printer.failures > 0 {
+        return Err(format!("{} pairs could not be aligned", printer.failures));
+    }
+
+    Ok(())
+}
+
+/// It writes the results of the non-interactive mode to the standard output.
+struct Printer<'a> {
+    /// It stores the parsed command-line arguments.
+    args: &'a Args,
+
+    /// It stores the locked standard output.
+    out: std::io::StdoutLock<'static>,
+
+    /// It stores the amount of pairs that could not be aligned.
+    failures: usize,
+}
+
+impl Printer<'_> {
+    /// Aligns every pair of records using `args.threads` threads, printing the
+    /// results in the same order as the pairs.
+    ///
+    /// If only the score is requested, then the pairs are scored without
+    /// building their alignments.
+    fn print_all(
+        &mut self,
+        aligner: &Aligner,
+        pairs: &[(&fasta::Record, &fasta::Record)],
+    ) -> Result<(), String> {
+        let threads = self.args.threads;
+
+        if self.args.format() == Format::Score {
+            let results = align_all(pairs, threads, |u, v| aligner.score(u, v));
+
+            for (&(u, v), result) in pairs.iter().zip(results) {
+                self.print_score(u, v, result)?;
+            }
+        } else {
+            let results = align_all(pairs, threads, |u, v| aligner.align(u, v));
+
+            for (&(u, v), result) in pairs.iter().zip(results) {
+                self.print(u, v, result)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value of the `result` of aligning the records `u` and `v`.
+    ///
+    /// In the batch modes, the pairs that could not be aligned are reported to
+    /// the standard error and skipped, returning `None`, otherwise the error is
+    /// returned.
+    fn check<T>(
+        &mut self,
+        u: &fasta::Record,
+        v: &fasta::Record,
+        result: Result<T, String>,
+    ) -> Result<Option<T>, String> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if self.args.batch != Batch::Single => {
+                eprintln!("{}\t{}: {}", u.id, v.id, err);
+                self.failures += 1;
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Prints the `result` of aligning the records `u` and `v`.
+    fn print(
+        &mut self,
+        u: &fasta::Record,
+        v: &fasta::Record,
+        result: Result<Alignment, String>,
+    ) -> Result<(), String> {
+        let batch = self.args.batch != Batch::Single;
+
+        let alignment = match self.check(u, v, result)? {
+            Some(alignment) => alignment,
+            None => return Ok(()),
+        };
+
+        let report = Report {
+            command: self.args.command,
+            u_id: u.id.clone(),
+            v_id: v.id.clone(),
+            v_len: v.sequence.len(),
+            alignment,
+        };
+
+        output::write(&mut self.out, self.args.format(), batch, &report)
+            .map_err(|err| err.to_string())
+    }
+
+    /// Prints the `result` of scoring the records `u` and `v`, that is, the
+    /// score and the end coordinates of their alignment.
+    fn print_score(
+        &mut self,
+        u: &fasta::Record,
+        v: &fasta::Record,
+        result: Result<(i32, usize, usize), String>,
+    ) -> Result<(), String> {
+        let (score, u_end, v_end) = match self.check(u, v, result)? {
+            Some(score) => score,
+            None => return Ok(()),
+        };
+
+        let report = ScoreReport {
+            u_id: u.id.clone(),
+            v_id: v.id.clone(),
+            score,
+            u_end,
+            v_end,
+        };
+
+        output::write_score(&mut self.out, &report).map_err(|err| err.to_string())
+    }
+}
+
+/// Returns the results of applying `f` to the sequences of every pair of
+/// records, in the same order as the pairs, distributing the pairs among
+/// `threads` threads.
+#[cfg(feature = "parallel")]
+fn align_all<T, F>(pairs: &[(&fasta::Record, &fasta::Record)], threads: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&[u8], &[u8]) -> T + Sync,
+{
+    let sequences = pairs
+        .iter()
+        .map(|(u, v)| (u.sequence.as_bytes(), v.sequence.as_bytes()))
+        .collect::<Vec<_>>();
+
+    map_pairs(&sequences, threads, f)
+}
+
+/// Returns the results of applying `f` to the sequences of every pair of
+/// records, in the same order as the pairs.
+#[cfg(not(feature = "parallel"))]
+fn align_all<T, F>(pairs: &[(&fasta::Record, &fasta::Record)], _threads: usize, f: F) -> Vec<T>
+where
+    F: Fn(&[u8], &[u8]) -> T,
+{
+    pairs
+        .iter()
+        .map(|(u, v)| f(u.sequence.as_bytes(), v.sequence.as_bytes()))
+        .collect()
+}
@@ -0,0 +1,227 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::cli::Command;
+use bioinformatics::sequencing::alignment::{Alignment, Operation};
+use bioinformatics::sequencing::cigar::Cigar;
+use std::io::{self, Write};
+
+/// The output format of the non-interactive mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable text.
+    Text,
+
+    /// One JSON object per line.
+    Json,
+
+    /// One BLAST-style tab-separated row per line.
+    Tsv,
+
+    /// One tab-separated row per line with only the score and the end
+    /// coordinates, so that no alignment needs to be built.
+    Score,
+}
+
+impl Format {
+    /// Returns the format named `name`, if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "tsv" => Some(Format::Tsv),
+            "score" => Some(Format::Score),
+            _ => None,
+        }
+    }
+}
+
+/// The result of running a command on a pair of sequences.
+///
+/// The `alignment` score is the alignment score for `global` and `local`, the
+/// length of the longest common subsequence for `lcs` and the distance for
+/// `hamming`, whose alignment has no gaps.
+pub struct Report {
+    /// It stores the command that produced the report.
+    pub command: Command,
+
+    /// It stores the identifier of the sequence `u`.
+    pub u_id: String,
+
+    /// It stores the identifier of the sequence `v`.
+    pub v_id: String,
+
+    /// It stores the length of the sequence `v`, used to soft clip the CIGAR.
+    pub v_len: usize,
+
+    /// It stores the alignment between the sequences.
+    pub alignment: Alignment,
+}
+
+/// The score of running a command on a pair of sequences, without the alignment.
+pub struct ScoreReport {
+    /// It stores the identifier of the sequence `u`.
+    pub u_id: String,
+
+    /// It stores the identifier of the sequence `v`.
+    pub v_id: String,
+
+    /// It stores the score, as in the [`Report`] alignment.
+    pub score: i32,
+
+    /// It stores the end coordinate of the alignment in `u`.
+    pub u_end: usize,
+
+    /// It stores the end coordinate of the alignment in `v`.
+    pub v_end: usize,
+}
+
+/// Writes the `report` to `out` using the specified `format`.
+///
+/// The JSON objects contain the zero-based, end-exclusive coordinates of the
+/// library, while the tabular rows contain the one-based, inclusive coordinates
+/// of BLAST, that is, the columns are `u_id`, `v_id`, `identity`, `length`,
+/// `mismatches`, `gap_opens`, `u_start`, `u_end`, `v_start`, `v_end` and `score`.
+///
+/// If `ids` is set, then the text format is preceded by a line with the
+/// identifiers of the sequences, so that the results of a batch may be told apart.
+pub fn write<W: Write>(out: &mut W, format: Format, ids: bool, report: &Report) -> io::Result<()> {
+    match format {
+        Format::Text if ids => {
+            writeln!(out, "# {}\t{}", report.u_id, report.v_id)?;
+            write_text(out, report)
+        }
+        Format::Text => write_text(out, report),
+        Format::Json => write_json(out, report),
+        Format::Tsv => write_tsv(out, report),
+        Format::Score => write_score(
+            out,
+            &ScoreReport {
+                u_id: report.u_id.clone(),
+                v_id: report.v_id.clone(),
+                score: report.alignment.score,
+                u_end: report.alignment.u_end,
+                v_end: report.alignment.v_end,
+            },
+        ),
+    }
+}
+
+/// Writes the `report` as a tab-separated row, whose columns are `u_id`, `v_id`,
+/// `score`, `u_end` and `v_end`.
+///
+/// The end coordinates are the same in the zero-based, end-exclusive coordinates
+/// of the library and in the one-based, inclusive coordinates of BLAST.
+pub fn write_score<W: Write>(out: &mut W, report: &ScoreReport) -> io::Result<()> {
+    writeln!(
+        out,
+        "{}\t{}\t{}\t{}\t{}",
+        report.u_id, report.v_id, report.score, report.u_end, report.v_end
+    )
+}
+
+/// Writes the `report` as human-readable text.
+fn write_text<W: Write>(out: &mut W, report: &Report) -> io::Result<()> {
+    let alignment = &report.alignment;
+
+    match report.command {
+        Command::Lcs => {
+            let lcs = alignment
+                .aligned_u
+                .chars()
+                .zip(alignment.operations.iter())
+                .filter(|(_, &op)| op == Operation::Match)
+                .map(|(c, _)| c)
+                .collect::<String>();
+
+            writeln!(out, "{}", lcs)
+        }
+        Command::Hamming => writeln!(out, "{}", alignment.score),
+        _ => writeln!(out, "Score: {}\n{}", alignment.score, alignment),
+    }
+}
+
+/// Writes the `report` as a JSON object in a single line.
+fn write_json<W: Write>(out: &mut W, report: &Report) -> io::Result<()> {
+    let alignment = &report.alignment;
+    let cigar = Cigar::from_alignment(alignment, report.v_len, true);
+
+    writeln!(
+        out,
+        "{{\"u_id\":{},\"v_id\":{},\"score\":{},\"aligned_u\":{},\"aligned_v\":{},\
+         \"u_start\":{},\"u_end\":{},\"v_start\":{},\"v_end\":{},\"identity\":{:.2},\"cigar\":{}}}",
+        json_string(&report.u_id),
+        json_string(&report.v_id),
+        alignment.score,
+        json_string(&alignment.aligned_u),
+        json_string(&alignment.aligned_v),
+        alignment.u_start,
+        alignment.u_end,
+        alignment.v_start,
+        alignment.v_end,
+        alignment.identity(),
+        json_string(&cigar.to_string())
+    )
+}
+
+/// Writes the `report` as a BLAST-style tab-separated row.
+fn write_tsv<W: Write>(out: &mut W, report: &Report) -> io::Result<()> {
+    let alignment = &report.alignment;
+
+    writeln!(
+        out,
+        "{}\t{}\t{:.2}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+        report.u_id,
+        report.v_id,
+        alignment.identity(),
+        alignment.operations.len(),
+        alignment.mismatches(),
+        alignment.gap_opens(),
+        alignment.u_start + 1,
+        alignment.u_end,
+        alignment.v_start + 1,
+        alignment.v_end,
+        alignment.score
+    )
+}
+
+/// Returns `s` as a quoted and escaped JSON string.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
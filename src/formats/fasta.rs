@@ -0,0 +1,238 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::formats::lines::{parse_header, Lines};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+/// A FASTA record.
+///
+/// It stores the identifier, that is, the first word of the header line,
+/// the optional description, that is, the remaining of the header line,
+/// and the sequence.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::formats::fasta::Record;
+/// let record = Record {
+///     id: String::from("seq1"),
+///     description: Some(String::from("a short sequence")),
+///     sequence: String::from("ACGT"),
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// It stores the identifier of the record.
+    pub id: String,
+
+    /// It stores the description of the record, if any.
+    pub description: Option<String>,
+
+    /// It stores the sequence of the record.
+    pub sequence: String,
+}
+
+/// A streaming FASTA reader.
+///
+/// It reads one record at a time from any [`BufRead`], hence files larger than
+/// the available memory may be processed. The sequence of a record may span
+/// multiple lines, blank lines are ignored, and the residues are converted
+/// to uppercase, so that they may be aligned regardless of their case.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::formats::fasta::Reader;
+/// # use bioinformatics::sequencing::global::{align_global, Options};
+/// let text = ">u first sequence\nAAAGA\ntacca\n\n>v\nGGGACTTAGA\n";
+///
+/// let records = Reader::new(text.as_bytes()).collect::<Result<Vec<_>, String>>().unwrap();
+///
+/// assert_eq!(records[0].id, "u");
+/// assert_eq!(records[0].description.as_deref(), Some("first sequence"));
+/// assert_eq!(records[0].sequence, "AAAGATACCA");
+///
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let (s, b) = align_global(&records[0].sequence, &records[1].sequence, &opt);
+///
+/// assert_eq!(s[10][10], 0);
+/// ```
+///
+/// Further, each record is a [`Result<Record, String>`] that will contain an
+/// error if the input cannot be read or it is malformed.
+pub struct Reader<R> {
+    /// It stores the lines of the underlying reader.
+    lines: Lines<R>,
+
+    /// It stores the header line of the next record, if it has been read.
+    header: Option<String>,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Constructs a new [`Reader`] that reads the records from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: Lines::new(reader),
+            header: None,
+        }
+    }
+
+    /// Returns the next record, or `None` at the end of the input.
+    fn read_record(&mut self) -> Result<Option<Record>, String> {
+        let header = match self.header.take() {
+            Some(header) => header,
+            None => match self.lines.next_non_blank()? {
+                Some(line) => line,
+                None => return Ok(None),
+            },
+        };
+
+        let (id, description) = parse_header(&header, '>', self.lines.number())?;
+
+        let mut record = Record {
+            id,
+            description,
+            sequence: String::new(),
+        };
+
+        while let Some(line) = self.lines.next_line()? {
+            if line.starts_with('>') {
+                self.header = Some(line);
+                break;
+            }
+
+            for c in line.chars().filter(|c| !c.is_whitespace()) {
+                if !c.is_ascii_alphabetic() && c != '*' && c != '-' {
+                    return Err(format!(
+                        "Line {}: Residue `{}` is not valid",
+                        self.lines.number(),
+                        c
+                    ));
+                }
+
+                record.sequence.push(c.to_ascii_uppercase());
+            }
+        }
+
+        Ok(Some(record))
+    }
+}
+
+impl Reader<BufReader<File>> {
+    /// Constructs a new [`Reader`] that reads the records from the file at `path`.
+    ///
+    /// Further, it is returned a [`Result<Reader, String>`] that will contain an
+    /// error if the file cannot be opened.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        match File::open(path.as_ref()) {
+            Ok(file) => Ok(Self::new(BufReader::new(file))),
+            Err(err) => Err(format!("{}: {}", path.as_ref().display(), err)),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Record, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// A FASTA writer.
+///
+/// It writes the records with the sequence wrapped in lines of, at most,
+/// `line_width` residues. A line width of `0` writes the whole sequence in
+/// a single line.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::formats::fasta::{Record, Writer};
+/// let mut writer = Writer::with_line_width(Vec::new(), 4);
+///
+/// writer
+///     .write(&Record {
+///         id: String::from("seq1"),
+///         description: Some(String::from("a short sequence")),
+///         sequence: String::from("ACGTACGTAC"),
+///     })
+///     .unwrap();
+///
+/// assert_eq!(writer.into_inner(), b">seq1 a short sequence\nACGT\nACGT\nAC\n");
+/// ```
+pub struct Writer<W> {
+    /// It stores the underlying writer.
+    writer: W,
+
+    /// It stores the maximum amount of residues per line.
+    line_width: usize,
+}
+
+impl<W: Write> Writer<W> {
+    /// Constructs a new [`Writer`] that writes the records to `writer`, with
+    /// lines of, at most, `60` residues.
+    pub fn new(writer: W) -> Self {
+        Self::with_line_width(writer, 60)
+    }
+
+    /// Constructs a new [`Writer`] that writes the records to `writer`, with
+    /// lines of, at most, `line_width` residues.
+    pub fn with_line_width(writer: W, line_width: usize) -> Self {
+        Self { writer, line_width }
+    }
+
+    /// Writes the `record`.
+    ///
+    /// Further, it is returned a [`Result<(), String>`] that will contain an
+    /// error if the record cannot be written.
+    pub fn write(&mut self, record: &Record) -> Result<(), String> {
+        self.write_record(record).map_err(|err| err.to_string())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes the `record`, propagating the I/O errors.
+    fn write_record(&mut self, record: &Record) -> std::io::Result<()> {
+        match &record.description {
+            Some(description) => writeln!(self.writer, ">{} {}", record.id, description)?,
+            None => writeln!(self.writer, ">{}", record.id)?,
+        }
+
+        if self.line_width == 0 {
+            return writeln!(self.writer, "{}", record.sequence);
+        }
+
+        for line in record.sequence.as_bytes().chunks(self.line_width) {
+            self.writer.write_all(line)?;
+            self.writer.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,409 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::formats::lines::{parse_header, Lines};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+/// The encoding of the qualities of a FASTQ file.
+///
+/// Each quality is a Phred score encoded as the ASCII character whose code
+/// is the score plus an offset, that is, `33` for the Sanger and recent
+/// Illumina files and `64` for the Illumina 1.3 to 1.7 files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Phred scores with offset `33`.
+    Phred33,
+
+    /// Phred scores with offset `64`.
+    Phred64,
+}
+
+impl Encoding {
+    /// Returns the offset added to the Phred scores.
+    pub fn offset(&self) -> u8 {
+        match self {
+            Encoding::Phred33 => 33,
+            Encoding::Phred64 => 64,
+        }
+    }
+
+    /// Returns the Phred score encoded by the character `c`, if it is valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::formats::fastq::Encoding;
+    /// assert_eq!(Encoding::Phred33.decode('I'), Some(40));
+    /// assert_eq!(Encoding::Phred64.decode('h'), Some(40));
+    /// assert_eq!(Encoding::Phred64.decode('#'), None);
+    /// ```
+    pub fn decode(&self, c: char) -> Option<u8> {
+        if c.is_ascii() && (c as u8) >= self.offset() && c != '\x7f' {
+            Some(c as u8 - self.offset())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the character that encodes the Phred score `quality`, if it
+    /// is representable.
+    pub fn encode(&self, quality: u8) -> Option<char> {
+        match quality.checked_add(self.offset()) {
+            Some(c) if c < 0x7f => Some(c as char),
+            _ => None,
+        }
+    }
+}
+
+/// A FASTQ record.
+///
+/// It stores the identifier, the optional description, the sequence and the
+/// decoded Phred score of each residue of the sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// It stores the identifier of the record.
+    pub id: String,
+
+    /// It stores the description of the record, if any.
+    pub description: Option<String>,
+
+    /// It stores the sequence of the record.
+    pub sequence: String,
+
+    /// It stores the Phred score of each residue of the sequence.
+    pub qualities: Vec<u8>,
+}
+
+impl Record {
+    /// Returns the mean Phred score of the record, or `0` if it is empty.
+    pub fn mean_quality(&self) -> f64 {
+        if self.qualities.is_empty() {
+            return 0.0;
+        }
+
+        self.qualities.iter().map(|&q| q as f64).sum::<f64>() / self.qualities.len() as f64
+    }
+
+    /// Removes the residues whose Phred score is lower than `threshold` from
+    /// both ends of the record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::formats::fastq::Record;
+    /// let mut record = Record {
+    ///     id: String::from("read1"),
+    ///     description: None,
+    ///     sequence: String::from("ACGTACGT"),
+    ///     qualities: vec![2, 10, 30, 35, 5, 30, 12, 3],
+    /// };
+    ///
+    /// record.trim_ends(10);
+    ///
+    /// assert_eq!(record.sequence, "CGTACG");
+    /// assert_eq!(record.qualities, vec![10, 30, 35, 5, 30, 12]);
+    /// ```
+    pub fn trim_ends(&mut self, threshold: u8) {
+        let start = self
+            .qualities
+            .iter()
+            .position(|&q| q >= threshold)
+            .unwrap_or(self.qualities.len());
+        let end = self
+            .qualities
+            .iter()
+            .rposition(|&q| q >= threshold)
+            .map_or(start, |k| k + 1);
+
+        self.retain(start, end);
+    }
+
+    /// Removes the low quality tail of the record using the algorithm of BWA.
+    ///
+    /// It removes the suffix that maximizes the sum of `threshold - q` over its
+    /// Phred scores `q`, hence a few good residues inside a bad tail do not stop
+    /// the trimming, unlike [`Record::trim_ends`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::formats::fastq::Record;
+    /// let mut record = Record {
+    ///     id: String::from("read1"),
+    ///     description: None,
+    ///     sequence: String::from("ACGTACGT"),
+    ///     qualities: vec![30, 30, 30, 30, 5, 21, 4, 3],
+    /// };
+    ///
+    /// record.trim_bwa(20);
+    ///
+    /// assert_eq!(record.sequence, "ACGT");
+    /// ```
+    pub fn trim_bwa(&mut self, threshold: u8) {
+        let mut end = self.qualities.len();
+
+        let mut sum = 0i64;
+        let mut max = 0i64;
+
+        for (k, &q) in self.qualities.iter().enumerate().rev() {
+            sum += threshold as i64 - q as i64;
+
+            if sum < 0 {
+                break;
+            }
+
+            if sum > max {
+                max = sum;
+                end = k;
+            }
+        }
+
+        self.retain(0, end);
+    }
+
+    /// Keeps only the residues from the position `start` to `end` (exclusive).
+    fn retain(&mut self, start: usize, end: usize) {
+        self.sequence = self
+            .sequence
+            .chars()
+            .skip(start)
+            .take(end - start)
+            .collect();
+        self.qualities.truncate(end);
+        self.qualities.drain(..start);
+    }
+}
+
+/// A streaming FASTQ reader.
+///
+/// It reads one record at a time from any [`BufRead`], in which each record
+/// comprises a header line starting with `@`, a sequence line, a separator
+/// line starting with `+` and a quality line. Blank lines between records
+/// are ignored, and the residues are converted to uppercase.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::formats::fastq::{Encoding, Reader};
+/// # use bioinformatics::sequencing::hamming;
+/// let text = "@read1 lane 1\nACGT\n+\nII#I\n@read2\nAGGT\n+read2\nIIII\n";
+///
+/// let records = Reader::new(text.as_bytes(), Encoding::Phred33)
+///     .collect::<Result<Vec<_>, String>>()
+///     .unwrap();
+///
+/// assert_eq!(records[0].qualities, vec![40, 40, 2, 40]);
+/// assert_eq!(hamming(&records[0].sequence, &records[1].sequence), Ok(1));
+/// ```
+///
+/// Further, each record is a [`Result<Record, String>`] that will contain an
+/// error if the input cannot be read or it is malformed, including when the
+/// sequence and quality lengths differ.
+pub struct Reader<R> {
+    /// It stores the lines of the underlying reader.
+    lines: Lines<R>,
+
+    /// It stores the encoding of the qualities.
+    encoding: Encoding,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Constructs a new [`Reader`] that reads the records from `reader`, whose
+    /// qualities are encoded using `encoding`.
+    pub fn new(reader: R, encoding: Encoding) -> Self {
+        Self {
+            lines: Lines::new(reader),
+            encoding,
+        }
+    }
+
+    /// Returns the next record, or `None` at the end of the input.
+    fn read_record(&mut self) -> Result<Option<Record>, String> {
+        let header = match self.lines.next_non_blank()? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+
+        let (id, description) = parse_header(&header, '@', self.lines.number())?;
+
+        let sequence = self.lines.expect_line("sequence")?;
+
+        if let Some(c) = sequence.chars().find(|c| !c.is_ascii_alphabetic()) {
+            return Err(format!(
+                "Line {}: Residue `{}` is not valid",
+                self.lines.number(),
+                c
+            ));
+        }
+
+        let separator = self.lines.expect_line("separator")?;
+
+        match separator.strip_prefix('+') {
+            Some(rest) if rest.is_empty() || rest.trim() == header[1..].trim() => {}
+            Some(_) => {
+                return Err(format!(
+                    "Line {}: Separator does not match the header",
+                    self.lines.number()
+                ))
+            }
+            None => {
+                return Err(format!(
+                    "Line {}: Expected a separator starting with `+`",
+                    self.lines.number()
+                ))
+            }
+        }
+
+        let quality = self.lines.expect_line("quality")?;
+
+        let qualities = quality
+            .chars()
+            .map(|c| {
+                self.encoding.decode(c).ok_or_else(|| {
+                    format!("Line {}: Quality `{}` is not valid", self.lines.number(), c)
+                })
+            })
+            .collect::<Result<Vec<u8>, String>>()?;
+
+        if qualities.len() != sequence.len() {
+            return Err(format!(
+                "Line {}: Sequence has {} residues, but {} qualities were found",
+                self.lines.number(),
+                sequence.len(),
+                qualities.len()
+            ));
+        }
+
+        Ok(Some(Record {
+            id,
+            description,
+            sequence: sequence.to_ascii_uppercase(),
+            qualities,
+        }))
+    }
+}
+
+impl Reader<BufReader<File>> {
+    /// Constructs a new [`Reader`] that reads the records from the file at `path`,
+    /// whose qualities are encoded using `encoding`.
+    ///
+    /// Further, it is returned a [`Result<Reader, String>`] that will contain an
+    /// error if the file cannot be opened.
+    pub fn from_file<P: AsRef<Path>>(path: P, encoding: Encoding) -> Result<Self, String> {
+        match File::open(path.as_ref()) {
+            Ok(file) => Ok(Self::new(BufReader::new(file), encoding)),
+            Err(err) => Err(format!("{}: {}", path.as_ref().display(), err)),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Record, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// A FASTQ writer.
+///
+/// It writes each record in four lines, with the qualities encoded using the
+/// specified [`Encoding`].
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::formats::fastq::{Encoding, Record, Writer};
+/// let mut writer = Writer::new(Vec::new(), Encoding::Phred64);
+///
+/// writer
+///     .write(&Record {
+///         id: String::from("read1"),
+///         description: None,
+///         sequence: String::from("ACGT"),
+///         qualities: vec![40, 40, 2, 40],
+///     })
+///     .unwrap();
+///
+/// assert_eq!(writer.into_inner(), b"@read1\nACGT\n+\nhhBh\n");
+/// ```
+pub struct Writer<W> {
+    /// It stores the underlying writer.
+    writer: W,
+
+    /// It stores the encoding of the qualities.
+    encoding: Encoding,
+}
+
+impl<W: Write> Writer<W> {
+    /// Constructs a new [`Writer`] that writes the records to `writer`, with
+    /// the qualities encoded using `encoding`.
+    pub fn new(writer: W, encoding: Encoding) -> Self {
+        Self { writer, encoding }
+    }
+
+    /// Writes the `record`.
+    ///
+    /// Further, it is returned a [`Result<(), String>`] that will contain an
+    /// error if the sequence and quality lengths differ, if a quality is not
+    /// representable in the encoding or if the record cannot be written.
+    pub fn write(&mut self, record: &Record) -> Result<(), String> {
+        if record.sequence.chars().count() != record.qualities.len() {
+            return Err(format!(
+                "Record `{}` has {} residues, but {} qualities",
+                record.id,
+                record.sequence.chars().count(),
+                record.qualities.len()
+            ));
+        }
+
+        let quality = record
+            .qualities
+            .iter()
+            .map(|&q| {
+                self.encoding
+                    .encode(q)
+                    .ok_or_else(|| format!("Quality {} is not representable", q))
+            })
+            .collect::<Result<String, String>>()?;
+
+        let header = match &record.description {
+            Some(description) => format!("@{} {}", record.id, description),
+            None => format!("@{}", record.id),
+        };
+
+        writeln!(
+            self.writer,
+            "{}\n{}\n+\n{}",
+            header, record.sequence, quality
+        )
+        .map_err(|err| err.to_string())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
@@ -0,0 +1,123 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::io::BufRead;
+
+/// A line reader shared by the FASTA and FASTQ readers.
+///
+/// It reads one line at a time from any [`BufRead`], keeping the number of the
+/// last line read, so that the errors may report the line in which they occur.
+pub(crate) struct Lines<R> {
+    /// It stores the underlying reader.
+    reader: R,
+
+    /// It stores the number of the last line read.
+    line: usize,
+}
+
+impl<R: BufRead> Lines<R> {
+    /// Constructs a new [`Lines`] that reads the lines from `reader`.
+    pub(crate) fn new(reader: R) -> Self {
+        Self { reader, line: 0 }
+    }
+
+    /// Returns the number of the last line read.
+    pub(crate) fn number(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the next line without its line terminator, or `None` at the
+    /// end of the input.
+    pub(crate) fn next_line(&mut self) -> Result<Option<String>, String> {
+        let mut line = String::new();
+
+        match self.reader.read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                self.line += 1;
+
+                let len = line.trim_end_matches(['\n', '\r']).len();
+                line.truncate(len);
+
+                Ok(Some(line))
+            }
+            Err(err) => Err(format!("Line {}: {}", self.line + 1, err)),
+        }
+    }
+
+    /// Returns the next line that is not blank, or `None` at the end of the input.
+    pub(crate) fn next_non_blank(&mut self) -> Result<Option<String>, String> {
+        loop {
+            match self.next_line()? {
+                Some(line) if line.trim().is_empty() => continue,
+                line => return Ok(line),
+            }
+        }
+    }
+
+    /// Returns the next line, failing at the end of the input, in which `what`
+    /// names the expected line.
+    pub(crate) fn expect_line(&mut self, what: &str) -> Result<String, String> {
+        match self.next_line()? {
+            Some(line) => Ok(line),
+            None => Err(format!("Line {}: Expected a {} line", self.line + 1, what)),
+        }
+    }
+}
+
+/// Returns the identifier and the description, if any, of the `header` line
+/// read at the line number `line`, that must start with `prefix`.
+///
+/// The identifier is the first word of the header, while the description is
+/// the remaining of it.
+pub(crate) fn parse_header(
+    header: &str,
+    prefix: char,
+    line: usize,
+) -> Result<(String, Option<String>), String> {
+    let header = match header.strip_prefix(prefix) {
+        Some(header) => header.trim(),
+        None => {
+            return Err(format!(
+                "Line {}: Expected a header starting with `{}`",
+                line, prefix
+            ))
+        }
+    };
+
+    let (id, description) = match header.split_once(char::is_whitespace) {
+        Some((id, description)) => (id, Some(description.trim())),
+        None => (header, None),
+    };
+
+    if id.is_empty() {
+        return Err(format!("Line {}: Header has no identifier", line));
+    }
+
+    Ok((
+        id.to_string(),
+        description
+            .filter(|description| !description.is_empty())
+            .map(String::from),
+    ))
+}
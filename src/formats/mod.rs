@@ -0,0 +1,3 @@
+pub mod fasta;
+pub mod fastq;
+mod lines;
@@ -27,9 +27,22 @@ use bioinformatics::sequencing::local;
 use std::env;
 use std::io::Write;
 
+mod cli;
+
 fn main() {
     let args: Vec<String> = env::args().collect();
 
+    // It runs the non-interactive mode if a command has been specified.
+    if args.len() > 1 && cli::Command::from_name(&args[1]).is_some() {
+        if let Err(message) = cli::Args::parse(&args[1..]).and_then(|args| cli::run(&args)) {
+            eprintln!("{}", message);
+            eprintln!("Run `bioinformatics help` for the usage.");
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
     let mut u_seq = String::from("");
     let mut v_seq = String::from("");
     let mut option = String::from("");
@@ -55,10 +68,24 @@ fn main() {
 
         // It removes the newline added.
         v_seq.pop();
+    } else if args.len() == 3 {
+        // The sequences have been populated from the command-line arguments,
+        // that may be either the sequences themselves or FASTA files.
+        match cli::read_sequence(&args[1])
+            .and_then(|u| cli::read_sequence(&args[2]).map(|v| (u, v)))
+        {
+            Ok((u, v)) => {
+                u_seq = u;
+                v_seq = v;
+            }
+            Err(message) => {
+                eprintln!("{}", message);
+                std::process::exit(1);
+            }
+        }
     } else {
-        // The sequences have been populated from the command-line arguments.
-        u_seq = String::from(&args[1]);
-        v_seq = String::from(&args[2]);
+        eprintln!("{}", cli::USAGE);
+        std::process::exit(1);
     }
 
     println!("\nWhich algorithm would you like to apply?: \n");
@@ -80,11 +107,11 @@ fn main() {
     match option.as_str() {
         "1" => {
             // It calculates the longest common subsequence.
-            let (_s, b) = lcs(u_seq.as_str(), v_seq.as_str());
+            let (s, b) = lcs(u_seq.as_str(), v_seq.as_str());
 
             // It terminates printing the longest common subsequence to the user.
             println!("\nThe longest common subsequence is: ");
-            print_lcs(b, u_seq.as_str());
+            print_lcs(&s, &b, u_seq.as_str(), v_seq.as_str());
         }
         "2" | "3" => {
             let mut match_str = String::from("");
@@ -129,14 +156,15 @@ fn main() {
                             match_,
                             mismatch,
                             gap,
+                            substitution: None,
                         },
                     );
 
                     println!(
                         "\nThe global sequence alignment with maximum score {} is: ",
-                        s[s.row() - 1][s.col() - 1]
+                        s[(s.row() - 1, s.col() - 1)]
                     );
-                    global::print_align_global(&b, &u_seq, &v_seq);
+                    global::print_align_global(&s, &b, &u_seq, &v_seq);
                 }
                 "3" => {
                     // It calculates the global alignment between the sequences.
@@ -147,6 +175,7 @@ fn main() {
                             match_,
                             mismatch,
                             gap,
+                            substitution: None,
                         },
                     );
 
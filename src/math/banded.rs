@@ -0,0 +1,186 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::ops::{Index, IndexMut};
+
+/// A matrix that only stores the cells of a diagonal band.
+///
+/// The band is specified by the range `[lo, hi]` of the diagonals `j - i`
+/// it contains. Therefore, the cell `(i, j)` is stored if, and only if,
+/// `lo <= j - i <= hi`, and only `row * (hi - lo + 1)` cells are stored
+/// instead of `row * col`.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::math::banded::BandedMatrix;
+/// let mut m = BandedMatrix::<i32>::new(4, 4, -1, 1, 0);
+/// m[(2, 3)] = 7;
+///
+/// assert!(m.contains(2, 3));
+/// assert!(!m.contains(0, 3));
+/// assert_eq!(m[(2, 3)], 7);
+/// assert_eq!(m.get(0, 3), None);
+/// ```
+///
+/// The code snippet above creates a `4` by `4` matrix that only stores the
+/// main diagonal and its adjacent diagonals.
+pub struct BandedMatrix<T> {
+    /// It stores the amount of rows this matrix has.
+    row: usize,
+
+    /// It stores the amount of columns this matrix has.
+    col: usize,
+
+    /// It stores the lowest diagonal in the band.
+    lo: isize,
+
+    /// It stores the highest diagonal in the band.
+    hi: isize,
+
+    /// It stores the band cells data, row by row.
+    matrix: Vec<T>,
+}
+
+impl<T> BandedMatrix<T> {
+    /// Returns the amount of rows.
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    /// Returns the amount of columns.
+    pub fn col(&self) -> usize {
+        self.col
+    }
+
+    /// Returns the lowest diagonal in the band.
+    pub fn lo(&self) -> isize {
+        self.lo
+    }
+
+    /// Returns the highest diagonal in the band.
+    pub fn hi(&self) -> isize {
+        self.hi
+    }
+
+    /// Returns whether the cell `(i, j)` is inside the band.
+    pub fn contains(&self, i: usize, j: usize) -> bool {
+        let d = j as isize - i as isize;
+
+        i < self.row && j < self.col && self.lo <= d && d <= self.hi
+    }
+
+    /// Returns the range of the columns of the `i`-th row that are inside
+    /// the band.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::math::banded::BandedMatrix;
+    /// let m = BandedMatrix::<i32>::new(4, 6, -1, 2, 0);
+    ///
+    /// assert_eq!(m.cols(0), 0..3);
+    /// assert_eq!(m.cols(3), 2..6);
+    /// ```
+    pub fn cols(&self, i: usize) -> std::ops::Range<usize> {
+        let start = (i as isize + self.lo).max(0) as usize;
+        let end = (i as isize + self.hi + 1).clamp(0, self.col as isize) as usize;
+
+        start..end.max(start)
+    }
+
+    /// Returns a reference to the cell `(i, j)`, if it is inside the band.
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        if self.contains(i, j) {
+            Some(&self.matrix[self.offset(i, j)])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the position of the cell `(i, j)` in the band cells data.
+    fn offset(&self, i: usize, j: usize) -> usize {
+        let width = (self.hi - self.lo + 1) as usize;
+
+        i * width + (j as isize - i as isize - self.lo) as usize
+    }
+}
+
+impl<T> BandedMatrix<T>
+where
+    T: Copy,
+{
+    /// Constructs a new `row` by `col` [`BandedMatrix`] that stores the diagonals
+    /// from `lo` to `hi`, initialized with `default_value`.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `lo` is greater than `hi`.
+    pub fn new(row: usize, col: usize, lo: isize, hi: isize, default_value: T) -> Self {
+        assert!(lo <= hi, "The band must contain at least one diagonal");
+
+        Self {
+            row,
+            col,
+            lo,
+            hi,
+            matrix: vec![default_value; row * (hi - lo + 1) as usize],
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for BandedMatrix<T> {
+    type Output = T;
+
+    /// Returns a reference to the cell `(i, j)`.
+    ///
+    /// It panics if the cell is not inside the band.
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(
+            self.contains(i, j),
+            "Cell ({}, {}) is outside the band",
+            i,
+            j
+        );
+
+        let offset = self.offset(i, j);
+        &self.matrix[offset]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for BandedMatrix<T> {
+    /// Returns a mutable reference to the cell `(i, j)`.
+    ///
+    /// It panics if the cell is not inside the band.
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            self.contains(i, j),
+            "Cell ({}, {}) is outside the band",
+            i,
+            j
+        );
+
+        let offset = self.offset(i, j);
+        &mut self.matrix[offset]
+    }
+}
@@ -25,9 +25,14 @@ use std::ops::{Index, IndexMut};
 
 /// An arbitrary dimension matrix.
 ///
+/// The cells are stored in a single contiguous buffer, row by row, hence
+/// the cells of a row are adjacent in memory and the whole matrix requires
+/// a single allocation.
+///
 /// # Examples
 ///
 /// ```
+/// # use bioinformatics::math::matrix::Matrix;
 /// let mut m = Matrix::<i32>::new(2, 3, 0);
 /// m[0][0] = 1;
 /// m[1][1] = 2;
@@ -35,12 +40,13 @@ use std::ops::{Index, IndexMut};
 /// assert_eq!(m.row(), 2);
 /// assert_eq!(m.col(), 3);
 /// assert_eq!(m[0][0], 1);
-/// assert_eq!(m[1][1], 2);
+/// assert_eq!(m[(1, 1)], 2);
 /// ```
 ///
 /// The code snippet above creates a 32-bit integer typed matrix
 /// with 2 rows and 3 columns with all of its values initialized
-/// to 0.
+/// to 0. The cells may be indexed either by `m[i][j]`, in which
+/// `m[i]` is the slice of the `i`-th row, or by `m[(i, j)]`.
 ///
 /// # Properties
 ///
@@ -58,6 +64,7 @@ use std::ops::{Index, IndexMut};
 /// For instance, suppose the following matrix definition.
 ///
 /// ```
+/// # use bioinformatics::math::matrix::Matrix;
 /// let m = Matrix::<i32>::new(4, 4, 1);
 ///
 /// m.print();
@@ -75,6 +82,7 @@ use std::ops::{Index, IndexMut};
 /// [`row`]: Matrix::row
 /// [`col`]: Matrix::col
 /// [`print`]: Matrix::print
+#[derive(Clone, Debug)]
 pub struct Matrix<T> {
     /// It stores the amount of rows this matrix has.
     row: usize,
@@ -82,8 +90,8 @@ pub struct Matrix<T> {
     /// It stores the amount of columns this matrix has.
     col: usize,
 
-    /// It stores the matrix cells data.
-    matrix: Vec<Vec<T>>,
+    /// It stores the matrix cells data, row by row.
+    matrix: Vec<T>,
 }
 
 impl<T> Matrix<T> {
@@ -92,6 +100,7 @@ impl<T> Matrix<T> {
     /// # Examples
     ///
     /// ```
+    /// # use bioinformatics::math::matrix::Matrix;
     /// let m = Matrix::<i32>::new(5, 4, 0);
     ///
     /// assert_eq!(m.row(), 5);
@@ -105,6 +114,7 @@ impl<T> Matrix<T> {
     /// # Examples
     ///
     /// ```
+    /// # use bioinformatics::math::matrix::Matrix;
     /// let m = Matrix::<i32>::new(5, 4, 0);
     ///
     /// assert_eq!(m.col(), 4);
@@ -112,6 +122,66 @@ impl<T> Matrix<T> {
     pub fn col(&self) -> usize {
         self.col
     }
+
+    /// Returns the cells of the matrix, row by row.
+    pub fn as_slice(&self) -> &[T] {
+        &self.matrix
+    }
+
+    /// Returns the `i`-th row and the one above it, that is, the `(i - 1)`-th
+    /// row, in which only the former is mutable.
+    ///
+    /// It allows the dynamic programming algorithms to calculate a row from
+    /// the previous one without indexing the matrix for each cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::math::matrix::Matrix;
+    /// let mut m = Matrix::<i32>::new(3, 3, 1);
+    ///
+    /// for i in 1..m.row() {
+    ///     let (previous, current) = m.row_pair_mut(i);
+    ///
+    ///     for j in 0..current.len() {
+    ///         current[j] = 2 * previous[j];
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(m[2], [4, 4, 4]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// It panics if `i` is `0` or it is not a row of the matrix.
+    pub fn row_pair_mut(&mut self, i: usize) -> (&[T], &mut [T]) {
+        assert!(i > 0 && i < self.row, "Row {} has no row above it", i);
+
+        let (above, below) = self.matrix.split_at_mut(i * self.col);
+
+        (&above[(i - 1) * self.col..], &mut below[..self.col])
+    }
+
+    /// Returns a reference to the cell `(i, j)`, without checking the bounds.
+    ///
+    /// # Safety
+    ///
+    /// The cell `(i, j)` must be inside the matrix, that is, `i < self.row()`
+    /// and `j < self.col()`.
+    pub unsafe fn get_unchecked(&self, i: usize, j: usize) -> &T {
+        self.matrix.get_unchecked(i * self.col + j)
+    }
+
+    /// Returns a mutable reference to the cell `(i, j)`, without checking the
+    /// bounds.
+    ///
+    /// # Safety
+    ///
+    /// The cell `(i, j)` must be inside the matrix, that is, `i < self.row()`
+    /// and `j < self.col()`.
+    pub unsafe fn get_unchecked_mut(&mut self, i: usize, j: usize) -> &mut T {
+        self.matrix.get_unchecked_mut(i * self.col + j)
+    }
 }
 
 impl<T> Matrix<T>
@@ -123,6 +193,7 @@ where
     /// # Examples
     ///
     /// ```
+    /// # use bioinformatics::math::matrix::Matrix;
     /// let m = Matrix::<i32>::new(2, 3, 10);
     /// ```
     ///
@@ -132,7 +203,7 @@ where
         Self {
             row,
             col,
-            matrix: vec![vec![default_value; col]; row],
+            matrix: vec![default_value; row * col],
         }
     }
 }
@@ -146,6 +217,7 @@ where
     /// # Examples
     ///
     /// ```
+    /// # use bioinformatics::math::matrix::Matrix;
     /// let m = Matrix::<i32>::new(4, 5, 1);
     ///
     /// m.print();
@@ -165,7 +237,7 @@ where
             for j in 0..self.col {
                 print!("{}", self[i][j]);
             }
-            println!("");
+            println!();
         }
     }
 }
@@ -179,27 +251,51 @@ where
     /// # Examples
     ///
     /// ```
+    /// # use bioinformatics::math::matrix::Matrix;
     /// let mut m = Matrix::<i32>::new(4, 4, 0);
     ///
     /// m[1][1] = 20;
     /// m[2][3] = 80;
     ///
-    /// assert_eq!(m.max(), 80);
+    /// assert_eq!(m.max(), &80);
     /// ```
     pub fn max(&self) -> &T {
-        let mut max_value: &T = &self[0][0];
+        let mut max_value: &T = &self.matrix[0];
 
-        for i in 0..self.row() {
-            for j in 0..self.col() {
-                if max_value < &self[i][j] {
-                    max_value = &self[i][j];
-                }
+        for value in self.matrix.iter() {
+            if max_value < value {
+                max_value = value;
             }
         }
 
         max_value
     }
     
+    /// Returns a reference to the minimum value in the matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::math::matrix::Matrix;
+    /// let mut m = Matrix::<i32>::new(4, 4, 0);
+    ///
+    /// m[1][1] = -20;
+    /// m[2][3] = -80;
+    ///
+    /// assert_eq!(m.min(), &-80);
+    /// ```
+    pub fn min(&self) -> &T {
+        let mut min_value: &T = &self.matrix[0];
+
+        for value in self.matrix.iter() {
+            if min_value > value {
+                min_value = value;
+            }
+        }
+
+        min_value
+    }
+
     /// Returns a tuple containing the reference to the maximum value as
     /// the first component, as the remaining components represents the
     /// `(i, j)` coordinates of the maximum value in the matrix.
@@ -207,6 +303,7 @@ where
     /// # Examples
     ///
     /// ```
+    /// # use bioinformatics::math::matrix::Matrix;
     /// let mut m = Matrix::<i32>::new(4, 4, 0);
     ///
     /// m[1][1] = 20;
@@ -214,41 +311,61 @@ where
     ///
     /// let (max_value, i, j) = m.max_with_pos();
     ///
-    /// assert_eq!(max_value, 80);
+    /// assert_eq!(max_value, &80);
     /// assert_eq!(i, 2);
     /// assert_eq!(j, 3);
     /// ```
     pub fn max_with_pos(&self) -> (&T, usize, usize) {
-        let mut max_value: &T = &self[0][0];
-        let mut i_pos = 0usize;
-        let mut j_pos = 0usize;
-
-        for i in 0..self.row() {
-            for j in 0..self.col() {
-                if max_value < &self[i][j] {
-                    max_value = &self[i][j];
-                    i_pos = i;
-                    j_pos = j;
-                }
+        let mut max_value: &T = &self.matrix[0];
+        let mut pos = 0usize;
+
+        for (k, value) in self.matrix.iter().enumerate() {
+            if max_value < value {
+                max_value = value;
+                pos = k;
             }
         }
 
-        (max_value, i_pos, j_pos)
+        (max_value, pos / self.col, pos % self.col)
     }
 }
 
 impl<T> Index<usize> for Matrix<T> {
-    type Output = Vec<T>;
+    type Output = [T];
 
     /// Returns a reference to the `row`-th matrix row.
     fn index(&self, row: usize) -> &Self::Output {
-        &self.matrix[row]
+        assert!(row < self.row, "Row {} is outside the matrix", row);
+
+        &self.matrix[row * self.col..(row + 1) * self.col]
     }
 }
 
 impl<T> IndexMut<usize> for Matrix<T> {
     /// Returns a mutable reference to the `row`-th matrix row.
     fn index_mut(&mut self, row: usize) -> &mut Self::Output {
-        &mut self.matrix[row]
+        assert!(row < self.row, "Row {} is outside the matrix", row);
+
+        &mut self.matrix[row * self.col..(row + 1) * self.col]
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    /// Returns a reference to the cell `(i, j)`.
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(j < self.col, "Column {} is outside the matrix", j);
+
+        &self.matrix[i * self.col + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    /// Returns a mutable reference to the cell `(i, j)`.
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(j < self.col, "Column {} is outside the matrix", j);
+
+        &mut self.matrix[i * self.col + j]
     }
 }
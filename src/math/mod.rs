@@ -1 +1,2 @@
+pub mod banded;
 pub mod matrix;
@@ -0,0 +1,284 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::math::matrix::Matrix;
+use crate::sequencing::traceback::Traceback;
+use std::fmt;
+
+/// An alignment operation, that is, a column of the alignment.
+///
+/// The operations are described taking the sequence `u` as the reference,
+/// hence an [`Operation::Insertion`] is a residue present only in `v` and
+/// a [`Operation::Deletion`] is a residue present only in `u`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    /// Two equal residues aligned against each other.
+    Match,
+
+    /// Two different residues aligned against each other.
+    Mismatch,
+
+    /// A residue of `v` aligned against a gap.
+    Insertion,
+
+    /// A residue of `u` aligned against a gap.
+    Deletion,
+}
+
+/// An alignment between the sequences `u` and `v`.
+///
+/// It stores the aligned rows, the alignment score, the coordinates of the
+/// aligned region in both sequences and the list of operations.
+///
+/// The coordinates are zero-based and the ends are exclusive, hence the
+/// aligned region of `u` is `u[u_start..u_end]`.
+///
+/// The sequences are read as bytes, that is, each byte is a residue and the
+/// coordinates are byte offsets. Therefore, the alignment functions accept any
+/// sequence that implements [`AsRef<[u8]>`], such as `&str`, `&[u8]` and `Vec<u8>`.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::{align_local, traceback_local, Options};
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let (s, b) = align_local("GGAGACCATTATG", "CCAATATG", &opt);
+///
+/// let alignment = traceback_local(&s, &b, "GGAGACCATTATG", "CCAATATG");
+///
+/// assert_eq!(alignment.aligned_u, "CCATTATG");
+/// assert_eq!(alignment.aligned_v, "CCAATATG");
+/// assert_eq!(alignment.score, 32);
+/// assert_eq!((alignment.u_start, alignment.u_end), (5, 13));
+/// assert_eq!((alignment.v_start, alignment.v_end), (0, 8));
+/// ```
+///
+/// # Printing
+///
+/// The alignment implements [`std::fmt::Display`], which formats the aligned
+/// rows in two lines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alignment {
+    /// It stores the aligned row of the sequence `u`.
+    pub aligned_u: String,
+
+    /// It stores the aligned row of the sequence `v`.
+    pub aligned_v: String,
+
+    /// It stores the alignment score.
+    pub score: i32,
+
+    /// It stores the position of `u` in which the alignment starts.
+    pub u_start: usize,
+
+    /// It stores the position of `u` in which the alignment ends (exclusive).
+    pub u_end: usize,
+
+    /// It stores the position of `v` in which the alignment starts.
+    pub v_start: usize,
+
+    /// It stores the position of `v` in which the alignment ends (exclusive).
+    pub v_end: usize,
+
+    /// It stores the alignment operations, one for each column.
+    pub operations: Vec<Operation>,
+}
+
+impl Alignment {
+    /// Constructs a new [`Alignment`] between the sequences `u` and `v` that
+    /// starts at `u_start` and `v_start` and applies the `operations`.
+    ///
+    /// The aligned rows and the end coordinates are built from the operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::sequencing::alignment::{Alignment, Operation};
+    /// use Operation::*;
+    ///
+    /// let alignment = Alignment::new("ACGT", "AGGTT", 0, 0, vec![Match, Mismatch, Match, Match, Insertion], 10);
+    ///
+    /// assert_eq!(alignment.aligned_u, "ACGT-");
+    /// assert_eq!(alignment.aligned_v, "AGGTT");
+    /// assert_eq!((alignment.u_end, alignment.v_end), (4, 5));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// It panics if the operations consume more residues than the sequences have.
+    pub fn new<U, V>(
+        u: U,
+        v: V,
+        u_start: usize,
+        v_start: usize,
+        operations: Vec<Operation>,
+        score: i32,
+    ) -> Self
+    where
+        U: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let mut u_chars = u.as_ref().iter().skip(u_start).map(|&c| char::from(c));
+        let mut v_chars = v.as_ref().iter().skip(v_start).map(|&c| char::from(c));
+
+        let mut aligned_u = String::with_capacity(operations.len());
+        let mut aligned_v = String::with_capacity(operations.len());
+
+        let mut u_end = u_start;
+        let mut v_end = v_start;
+
+        for op in operations.iter() {
+            match op {
+                Operation::Match | Operation::Mismatch => {
+                    aligned_u.push(u_chars.next().expect("Operations exceed the sequence `u`"));
+                    aligned_v.push(v_chars.next().expect("Operations exceed the sequence `v`"));
+                    u_end += 1;
+                    v_end += 1;
+                }
+                Operation::Insertion => {
+                    aligned_u.push('-');
+                    aligned_v.push(v_chars.next().expect("Operations exceed the sequence `v`"));
+                    v_end += 1;
+                }
+                Operation::Deletion => {
+                    aligned_u.push(u_chars.next().expect("Operations exceed the sequence `u`"));
+                    aligned_v.push('-');
+                    u_end += 1;
+                }
+            }
+        }
+
+        Self {
+            aligned_u,
+            aligned_v,
+            score,
+            u_start,
+            u_end,
+            v_start,
+            v_end,
+            operations,
+        }
+    }
+
+    /// Returns the percentage of the alignment columns that are matches, or `0`
+    /// if the alignment is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::sequencing::alignment::{Alignment, Operation};
+    /// use Operation::*;
+    ///
+    /// let alignment = Alignment::new("ACGT", "AGGTT", 0, 0, vec![Match, Mismatch, Match, Match, Insertion], 10);
+    ///
+    /// assert_eq!(alignment.identity(), 60.0);
+    /// assert_eq!(alignment.mismatches(), 1);
+    /// assert_eq!(alignment.gap_opens(), 1);
+    /// ```
+    pub fn identity(&self) -> f64 {
+        if self.operations.is_empty() {
+            return 0.0;
+        }
+
+        let matches = self
+            .operations
+            .iter()
+            .filter(|&&op| op == Operation::Match)
+            .count();
+
+        100.0 * matches as f64 / self.operations.len() as f64
+    }
+
+    /// Returns the amount of mismatch columns.
+    pub fn mismatches(&self) -> usize {
+        self.operations
+            .iter()
+            .filter(|&&op| op == Operation::Mismatch)
+            .count()
+    }
+
+    /// Returns the amount of gaps, that is, of maximal runs of insertions or
+    /// of deletions.
+    pub fn gap_opens(&self) -> usize {
+        let mut gaps = 0;
+        let mut last = None;
+
+        for &op in self.operations.iter() {
+            if matches!(op, Operation::Insertion | Operation::Deletion) && last != Some(op) {
+                gaps += 1;
+            }
+
+            last = Some(op);
+        }
+
+        gaps
+    }
+
+    /// Constructs the [`Alignment`] by backtracking the traceback matrix `b`, following
+    /// the preferred predecessor of each cell, from the cell `(i, j)` until the cell
+    /// `(0, 0)` or a cell with no predecessor is found.
+    pub(crate) fn from_traceback(
+        b: &Matrix<Traceback>,
+        u: &[u8],
+        v: &[u8],
+        mut i: usize,
+        mut j: usize,
+        score: i32,
+    ) -> Self {
+        let mut operations = Vec::new();
+
+        while i > 0 || j > 0 {
+            match b[(i, j)].preferred() {
+                Traceback::DIAGONAL => {
+                    operations.push(if u[i - 1] == v[j - 1] {
+                        Operation::Match
+                    } else {
+                        Operation::Mismatch
+                    });
+                    i -= 1;
+                    j -= 1;
+                }
+                Traceback::UP => {
+                    operations.push(Operation::Deletion);
+                    i -= 1;
+                }
+                Traceback::LEFT => {
+                    operations.push(Operation::Insertion);
+                    j -= 1;
+                }
+                _ => break,
+            }
+        }
+
+        operations.reverse();
+
+        Self::new(u, v, i, j, operations, score)
+    }
+}
+
+impl fmt::Display for Alignment {
+    /// Formats the aligned rows in two lines.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n{}", self.aligned_u, self.aligned_v)
+    }
+}
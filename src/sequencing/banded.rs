@@ -0,0 +1,363 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::math::banded::BandedMatrix;
+use crate::sequencing::alignment::{Alignment, Operation};
+use crate::sequencing::scoring::NEG_INF;
+use crate::sequencing::traceback::Traceback;
+use crate::sequencing::{global, local};
+
+/// Banded Alignment Options.
+///
+/// This structure provides a manner to specify the `width` of the diagonal
+/// band in which the alignment is calculated, and whether the band must be
+/// automatically widened until the alignment score is proven optimal.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::banded::Band;
+/// let band = Band {
+///     width: 8,
+///     widen: true,
+/// };
+/// ```
+///
+/// The code snippet above defines a band containing the diagonals up to `8`
+/// cells away from the diagonals of the first and the last cells, that is
+/// doubled until the alignment score is proven optimal.
+pub struct Band {
+    pub width: usize,
+    pub widen: bool,
+}
+
+/// Align two sequences globally inside a diagonal band.
+///
+/// It applies the Needleman-Wunsch algorithm only to the cells whose diagonal
+/// `j - i` is at most `band.width` away from the diagonals `0` and `m - n`,
+/// in which the alignment starts and ends. Therefore, it takes `O((n + m) * width)`
+/// time and memory, which is much less than [`global::align_global`] for
+/// near-identical sequences.
+///
+/// If `band.widen` is set, then the band width is doubled until the score is
+/// greater than or equal to an upper bound of the score of any alignment that
+/// leaves the band, hence the returned alignment is optimal. Otherwise, the
+/// returned alignment is the best one inside the band. Since the bound requires
+/// a non-positive gap point, a positive one widens the band to the whole matrix.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::banded::{align_global_banded, Band};
+/// # use bioinformatics::sequencing::global::Options;
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let alignment = align_global_banded("ACGTACGTTACG", "ACGTACGTACG", &opt, &Band { width: 2, widen: true });
+///
+/// assert_eq!(alignment.aligned_v, "ACGTACG-TACG");
+/// assert_eq!(alignment.score, 51);
+/// ```
+pub fn align_global_banded<U, V>(u: U, v: V, opt: &global::Options, band: &Band) -> Alignment
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let (n, m) = (u.len(), v.len());
+    let gap = opt.gap as i32;
+
+    let mut width = band.width;
+
+    // The score bounds only hold for a non-positive gap point, since otherwise
+    // an alignment with more gaps may score better, hence the band is widened
+    // to the whole matrix at once.
+    if band.widen && gap > 0 {
+        width = width.max(n.max(m));
+    }
+
+    loop {
+        let (lo, hi) = diagonals(n, m, width);
+
+        let mut s = BandedMatrix::<i32>::new(n + 1, m + 1, lo, hi, NEG_INF);
+        let mut b = BandedMatrix::<Traceback>::new(n + 1, m + 1, lo, hi, Traceback::NONE);
+
+        for i in 0..s.row() {
+            for j in s.cols(i) {
+                if i == 0 && j == 0 {
+                    s[(i, j)] = 0;
+                    continue;
+                }
+
+                if i == 0 {
+                    s[(i, j)] = (j as i32) * gap;
+                    b[(i, j)] = Traceback::LEFT;
+                    continue;
+                }
+
+                if j == 0 {
+                    s[(i, j)] = (i as i32) * gap;
+                    b[(i, j)] = Traceback::UP;
+                    continue;
+                }
+
+                let (diagonal, upper, left) =
+                    neighbours(&s, u, v, i, j, gap, |a, b| opt.score(a, b));
+
+                let best = diagonal.max(upper).max(left);
+
+                s[(i, j)] = best;
+                b[(i, j)] = Traceback::from_scores(best, diagonal, upper, left);
+            }
+        }
+
+        let score = s[(n, m)];
+
+        // It checks whether no alignment leaving the band may score better
+        // than the one found inside the band.
+        let optimal = match global_bound(n, m, lo, hi, opt.max_score(), gap) {
+            Some(bound) => score >= bound,
+            None => true,
+        };
+
+        if !band.widen || optimal {
+            return traceback(&b, u, v, n, m, score);
+        }
+
+        width = (width * 2).max(1);
+    }
+}
+
+/// Align two sequences locally inside a diagonal band.
+///
+/// It applies the Smith-Waterman algorithm only to the cells whose diagonal
+/// `j - i` is at most `band.width` away from the diagonals `0` and `m - n`.
+/// Therefore, it takes `O((n + m) * width)` time and memory.
+///
+/// If `band.widen` is set, then the band width is doubled until the score is
+/// greater than or equal to an upper bound of the score of any alignment that
+/// leaves the band, hence the returned alignment is optimal. Since a local
+/// alignment may lie anywhere in the matrix, this bound is looser than the one
+/// of [`align_global_banded`], hence the band is usually widened further. As in
+/// [`align_global_banded`], a positive gap point widens the band to the whole matrix.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::banded::{align_local_banded, Band};
+/// # use bioinformatics::sequencing::local::Options;
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let alignment = align_local_banded("GGAGACCATTATG", "GGAGCCATTATG", &opt, &Band { width: 2, widen: false });
+///
+/// assert_eq!(alignment.aligned_v, "GGAG-CCATTATG");
+/// assert_eq!(alignment.score, 56);
+/// ```
+pub fn align_local_banded<U, V>(u: U, v: V, opt: &local::Options, band: &Band) -> Alignment
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let (n, m) = (u.len(), v.len());
+    let gap = opt.gap as i32;
+
+    let mut width = band.width;
+
+    // The score bounds only hold for a non-positive gap point, since otherwise
+    // an alignment with more gaps may score better, hence the band is widened
+    // to the whole matrix at once.
+    if band.widen && gap > 0 {
+        width = width.max(n.max(m));
+    }
+
+    loop {
+        let (lo, hi) = diagonals(n, m, width);
+
+        let mut s = BandedMatrix::<i32>::new(n + 1, m + 1, lo, hi, 0);
+        let mut b = BandedMatrix::<Traceback>::new(n + 1, m + 1, lo, hi, Traceback::NONE);
+
+        let (mut max_i, mut max_j) = (0usize, 0usize);
+
+        for i in 1..s.row() {
+            for j in s.cols(i) {
+                if j == 0 {
+                    continue;
+                }
+
+                let (diagonal, upper, left) =
+                    neighbours(&s, u, v, i, j, gap, |a, b| opt.score(a, b));
+
+                if diagonal < 0 && upper < 0 && left < 0 {
+                    s[(i, j)] = 0;
+                    continue;
+                }
+
+                let best = diagonal.max(upper).max(left);
+
+                s[(i, j)] = best;
+                b[(i, j)] = Traceback::from_scores(best, diagonal, upper, left);
+
+                if s[(i, j)] > s[(max_i, max_j)] {
+                    max_i = i;
+                    max_j = j;
+                }
+            }
+        }
+
+        let score = s[(max_i, max_j)];
+
+        // It checks whether no alignment leaving the band may score better
+        // than the one found inside the band.
+        let optimal = match local_bound(n, m, lo, hi, opt.max_score(), gap) {
+            Some(bound) => score >= bound,
+            None => true,
+        };
+
+        if !band.widen || optimal {
+            return traceback(&b, u, v, max_i, max_j, score);
+        }
+
+        width = (width * 2).max(1);
+    }
+}
+
+/// Returns the range `[lo, hi]` of the diagonals inside the band, that is,
+/// the diagonals at most `width` away from the diagonal `0`, in which the
+/// first cell is, and from the diagonal `m - n`, in which the last cell is.
+fn diagonals(n: usize, m: usize, width: usize) -> (isize, isize) {
+    let (n, m, width) = (n as isize, m as isize, width as isize);
+
+    let lo = (0.min(m - n) - width).max(-n);
+    let hi = (0.max(m - n) + width).min(m);
+
+    (lo, hi)
+}
+
+/// Returns the `diagonal`, `upper` and `left` candidate scores of the cell
+/// `(i, j)`, in which the neighbours outside the band are unreachable.
+fn neighbours<F>(
+    s: &BandedMatrix<i32>,
+    u: &[u8],
+    v: &[u8],
+    i: usize,
+    j: usize,
+    gap: i32,
+    score: F,
+) -> (i32, i32, i32)
+where
+    F: Fn(u8, u8) -> i32,
+{
+    // The upper left neighbour is in the same diagonal, hence it is always
+    // inside the band.
+    let diagonal = s[(i - 1, j - 1)] + score(u[i - 1], v[j - 1]);
+    let upper = s.get(i - 1, j).map_or(NEG_INF, |&x| x + gap);
+    let left = s.get(i, j - 1).map_or(NEG_INF, |&x| x + gap);
+
+    (diagonal, upper, left)
+}
+
+/// Returns an upper bound of the score of any global alignment that leaves
+/// the band `[lo, hi]`, or `None` if the band contains the whole matrix.
+///
+/// Since each gap moves the alignment to an adjacent diagonal, an alignment
+/// that visits the diagonal `d` has at least `|d| + |d - (m - n)|` gaps. Moreover,
+/// an alignment with `g` gaps has `(n + m - g) / 2` aligned residues. The bound
+/// requires the `gap` point to be non-positive.
+fn global_bound(n: usize, m: usize, lo: isize, hi: isize, max_score: i32, gap: i32) -> Option<i32> {
+    let (n, m) = (n as isize, m as isize);
+
+    let gaps = |d: isize| d.abs() + (d - (m - n)).abs();
+
+    let min_gaps = match (hi < m, lo > -n) {
+        (true, true) => gaps(hi + 1).min(gaps(lo - 1)),
+        (true, false) => gaps(hi + 1),
+        (false, true) => gaps(lo - 1),
+        (false, false) => return None,
+    };
+
+    let aligned = ((n + m - min_gaps) / 2).max(0) as i32;
+
+    Some(aligned * max_score.max(0) + (min_gaps as i32) * gap)
+}
+
+/// Returns an upper bound of the score of any local alignment that leaves
+/// the band `[lo, hi]`, or `None` if the band contains the whole matrix.
+///
+/// An alignment entirely outside the band has, at most, as many aligned
+/// residues as the longest diagonal outside the band, while an alignment
+/// partially inside it has at least one gap. The bound requires the `gap` point
+/// to be non-positive.
+fn local_bound(n: usize, m: usize, lo: isize, hi: isize, max_score: i32, gap: i32) -> Option<i32> {
+    let (n, m) = (n as isize, m as isize);
+
+    if hi >= m && lo <= -n {
+        return None;
+    }
+
+    let outside = n.min(m - hi - 1).max(0).max(m.min(n + lo - 1).max(0)) as i32;
+    let crossing = n.min(m) as i32;
+
+    let max_score = max_score.max(0);
+
+    Some((outside * max_score).max(crossing * max_score + gap))
+}
+
+/// Constructs the [`Alignment`] by backtracking the banded [`Traceback`] matrix `b`
+/// from the cell `(i, j)` until the cell `(0, 0)` or a cell with no predecessor.
+fn traceback(
+    b: &BandedMatrix<Traceback>,
+    u: &[u8],
+    v: &[u8],
+    mut i: usize,
+    mut j: usize,
+    score: i32,
+) -> Alignment {
+    let mut operations = Vec::new();
+
+    while i > 0 || j > 0 {
+        match b[(i, j)].preferred() {
+            Traceback::DIAGONAL => {
+                operations.push(if u[i - 1] == v[j - 1] {
+                    Operation::Match
+                } else {
+                    Operation::Mismatch
+                });
+                i -= 1;
+                j -= 1;
+            }
+            Traceback::UP => {
+                operations.push(Operation::Deletion);
+                i -= 1;
+            }
+            Traceback::LEFT => {
+                operations.push(Operation::Insertion);
+                j -= 1;
+            }
+            _ => break,
+        }
+    }
+
+    operations.reverse();
+
+    Alignment::new(u, v, i, j, operations, score)
+}
@@ -0,0 +1,331 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::sequencing::alignment::{Alignment, Operation};
+use std::fmt;
+
+/// A CIGAR operation.
+///
+/// As in the SAM format, the sequence `u` is taken as the reference and the
+/// sequence `v` as the query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CigarOp {
+    /// Residues aligned against each other, that may be equal or not (`M`).
+    Match,
+
+    /// Equal residues aligned against each other (`=`).
+    Equal,
+
+    /// Different residues aligned against each other (`X`).
+    Diff,
+
+    /// Residues present only in `v` (`I`).
+    Insertion,
+
+    /// Residues present only in `u` (`D`).
+    Deletion,
+
+    /// Residues of `v` left out of the alignment (`S`).
+    SoftClip,
+}
+
+impl CigarOp {
+    /// Returns the character that represents the operation.
+    pub fn to_char(self) -> char {
+        match self {
+            CigarOp::Match => 'M',
+            CigarOp::Equal => '=',
+            CigarOp::Diff => 'X',
+            CigarOp::Insertion => 'I',
+            CigarOp::Deletion => 'D',
+            CigarOp::SoftClip => 'S',
+        }
+    }
+
+    /// Returns the operation represented by the character `c`, if any.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'M' => Some(CigarOp::Match),
+            '=' => Some(CigarOp::Equal),
+            'X' => Some(CigarOp::Diff),
+            'I' => Some(CigarOp::Insertion),
+            'D' => Some(CigarOp::Deletion),
+            'S' => Some(CigarOp::SoftClip),
+            _ => None,
+        }
+    }
+}
+
+/// A CIGAR string, that is, a run-length encoded list of [`CigarOp`]s.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::cigar::Cigar;
+/// # use bioinformatics::sequencing::local::{align_local, traceback_local, Options};
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let (s, b) = align_local("GGAGACCATTATG", "TTCCAATATGTT", &opt);
+///
+/// let alignment = traceback_local(&s, &b, "GGAGACCATTATG", "TTCCAATATGTT");
+///
+/// assert_eq!(Cigar::from_alignment(&alignment, 12, true).to_string(), "2S3=1X4=2S");
+/// assert_eq!(Cigar::from_alignment(&alignment, 12, false).to_string(), "2S8M2S");
+/// ```
+///
+/// The code snippet above builds the extended and the basic CIGAR strings of a local
+/// alignment, in which the unaligned ends of the query `v` are soft clipped.
+///
+/// An empty CIGAR string is formatted as `*`, as in the SAM format.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cigar {
+    /// It stores the operations and their lengths.
+    pub operations: Vec<(usize, CigarOp)>,
+}
+
+impl Cigar {
+    /// Constructs the [`Cigar`] of the `alignment`, in which `v_len` is the
+    /// length of the sequence `v`.
+    ///
+    /// If `extended` is set, then the aligned residues are encoded as `=` and `X`,
+    /// otherwise they are encoded as `M`. The residues of `v` before and after the
+    /// aligned region are encoded as `S`.
+    ///
+    /// # Panics
+    ///
+    /// It panics if the alignment ends after `v_len`.
+    pub fn from_alignment(alignment: &Alignment, v_len: usize, extended: bool) -> Self {
+        assert!(
+            alignment.v_end <= v_len,
+            "The alignment exceeds the sequence `v`"
+        );
+
+        let mut cigar = Self::default();
+
+        cigar.push(alignment.v_start, CigarOp::SoftClip);
+
+        for op in alignment.operations.iter() {
+            let op = match op {
+                Operation::Match if extended => CigarOp::Equal,
+                Operation::Mismatch if extended => CigarOp::Diff,
+                Operation::Match | Operation::Mismatch => CigarOp::Match,
+                Operation::Insertion => CigarOp::Insertion,
+                Operation::Deletion => CigarOp::Deletion,
+            };
+
+            cigar.push(1, op);
+        }
+
+        cigar.push(v_len - alignment.v_end, CigarOp::SoftClip);
+
+        cigar
+    }
+
+    /// Parses a CIGAR string, such as `3S5=1X2I4M`.
+    ///
+    /// The string `*` is parsed as an empty CIGAR string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::sequencing::cigar::{Cigar, CigarOp};
+    /// let cigar = Cigar::parse("3=1X2I").unwrap();
+    ///
+    /// assert_eq!(cigar.operations, vec![(3, CigarOp::Equal), (1, CigarOp::Diff), (2, CigarOp::Insertion)]);
+    /// assert!(Cigar::parse("3=1H").is_err());
+    /// ```
+    ///
+    /// Further, it is returned a [`Result<Cigar, String>`] that will contain an
+    /// error if an operation is unknown, has no length or has a zero length.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut cigar = Self::default();
+
+        if text == "*" {
+            return Ok(cigar);
+        }
+
+        let mut len = String::new();
+
+        for c in text.chars() {
+            if c.is_ascii_digit() {
+                len.push(c);
+                continue;
+            }
+
+            let op = match CigarOp::from_char(c) {
+                Some(op) => op,
+                None => return Err(format!("Operation `{}` is not supported", c)),
+            };
+
+            let n = match len.parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                Ok(_) => return Err(format!("Operation `{}` has a zero length", c)),
+                Err(_) => return Err(format!("Operation `{}` has no length", c)),
+            };
+
+            cigar.operations.push((n, op));
+            len.clear();
+        }
+
+        if !len.is_empty() {
+            return Err(format!("Length `{}` has no operation", len));
+        }
+
+        Ok(cigar)
+    }
+
+    /// Constructs the [`Alignment`] between the sequences `u` and `v` described
+    /// by this CIGAR string, that starts at the position `u_start` of `u`.
+    ///
+    /// Since the score is not encoded in a CIGAR string, the score of the returned
+    /// alignment is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::sequencing::cigar::Cigar;
+    /// let cigar = Cigar::parse("2S3=1X4=2S").unwrap();
+    /// let alignment = cigar.to_alignment("GGAGACCATTATG", "TTCCAATATGTT", 5).unwrap();
+    ///
+    /// assert_eq!(alignment.aligned_u, "CCATTATG");
+    /// assert_eq!(alignment.aligned_v, "CCAATATG");
+    /// assert_eq!((alignment.v_start, alignment.v_end), (2, 10));
+    /// ```
+    ///
+    /// Further, it is returned a [`Result<Alignment, String>`] that will contain an
+    /// error if a soft clip is not at an end, if the operations exceed a sequence,
+    /// if the operations and soft clips do not cover the whole sequence `v`, or if
+    /// an `=` (`X`) operation aligns different (equal) residues.
+    pub fn to_alignment<U, V>(&self, u: U, v: V, u_start: usize) -> Result<Alignment, String>
+    where
+        U: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let (u, v) = (u.as_ref(), v.as_ref());
+
+        let last = self.operations.len().saturating_sub(1);
+
+        let mut operations = Vec::new();
+
+        let (mut i, mut j) = (u_start, 0);
+        let mut v_start = 0;
+
+        for (k, &(n, op)) in self.operations.iter().enumerate() {
+            let (di, dj) = match op {
+                CigarOp::SoftClip => (0, n),
+                CigarOp::Insertion => (0, n),
+                CigarOp::Deletion => (n, 0),
+                CigarOp::Match | CigarOp::Equal | CigarOp::Diff => (n, n),
+            };
+
+            if i + di > u.len() {
+                return Err(format!(
+                    "CIGAR exceeds the sequence `u` of length {}",
+                    u.len()
+                ));
+            }
+
+            if j + dj > v.len() {
+                return Err(format!(
+                    "CIGAR exceeds the sequence `v` of length {}",
+                    v.len()
+                ));
+            }
+
+            match op {
+                CigarOp::SoftClip if k == 0 => v_start = n,
+                CigarOp::SoftClip if k == last => {}
+                CigarOp::SoftClip => return Err(String::from("Soft clips must be at the ends")),
+                CigarOp::Insertion => {
+                    operations.extend(std::iter::repeat_n(Operation::Insertion, n))
+                }
+                CigarOp::Deletion => operations.extend(std::iter::repeat_n(Operation::Deletion, n)),
+                CigarOp::Match | CigarOp::Equal | CigarOp::Diff => {
+                    for (a, b) in u[i..i + n].iter().zip(v[j..j + n].iter()) {
+                        if op == CigarOp::Equal && a != b {
+                            return Err(format!(
+                                "Residues `{}` and `{}` are not equal",
+                                a.escape_ascii(),
+                                b.escape_ascii()
+                            ));
+                        }
+
+                        if op == CigarOp::Diff && a == b {
+                            return Err(format!(
+                                "Residues `{}` and `{}` are equal",
+                                a.escape_ascii(),
+                                b.escape_ascii()
+                            ));
+                        }
+
+                        operations.push(if a == b {
+                            Operation::Match
+                        } else {
+                            Operation::Mismatch
+                        });
+                    }
+                }
+            }
+
+            i += di;
+            j += dj;
+        }
+
+        if j != v.len() {
+            return Err(format!(
+                "CIGAR covers {} residues of `v`, but it has {}",
+                j,
+                v.len()
+            ));
+        }
+
+        Ok(Alignment::new(u, v, u_start, v_start, operations, 0))
+    }
+
+    /// Appends `n` operations `op`, merging them with the last run if it has
+    /// the same operation.
+    fn push(&mut self, n: usize, op: CigarOp) {
+        if n == 0 {
+            return;
+        }
+
+        match self.operations.last_mut() {
+            Some((len, last)) if *last == op => *len += n,
+            _ => self.operations.push((n, op)),
+        }
+    }
+}
+
+impl fmt::Display for Cigar {
+    /// Formats the CIGAR string, or `*` if it is empty.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.operations.is_empty() {
+            return write!(f, "*");
+        }
+
+        for (n, op) in self.operations.iter() {
+            write!(f, "{}{}", n, op.to_char())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::math::matrix::Matrix;
+use crate::sequencing::alignment::{Alignment, Operation};
+use crate::sequencing::traceback::Traceback;
+
+/// An iterator over every co-optimal alignment.
+///
+/// It backtracks the [`Traceback`] matrix, in which each cell stores all of
+/// its optimal predecessors, from each of the cells in which an optimal
+/// alignment ends, until a cell with no predecessor is found.
+///
+/// The alignments are yielded in depth-first order, preferring the diagonal,
+/// then the upper and then the left predecessor. Hence, the first alignment
+/// is the same one built by the single traceback functions.
+///
+/// Since the amount of co-optimal alignments may grow exponentially with the
+/// length of the sequences, an optional `limit` on the amount of yielded
+/// alignments may be specified.
+pub struct CoOptimalAlignments<'a> {
+    /// It stores the predecessor matrix.
+    b: &'a Matrix<Traceback>,
+
+    /// It stores the sequence `u`.
+    u: &'a [u8],
+
+    /// It stores the sequence `v`.
+    v: &'a [u8],
+
+    /// It stores the cells in which an optimal alignment ends.
+    ends: std::vec::IntoIter<(usize, usize)>,
+
+    /// It stores the optimal score.
+    score: i32,
+
+    /// It stores the cells of the current path, with the predecessors
+    /// not yet visited of each one.
+    stack: Vec<(usize, usize, Traceback)>,
+
+    /// It stores the operations of the current path, from its end.
+    operations: Vec<Operation>,
+
+    /// It stores the amount of alignments that may still be yielded.
+    remaining: Option<usize>,
+}
+
+impl<'a> CoOptimalAlignments<'a> {
+    /// Constructs a new [`CoOptimalAlignments`] that backtracks `b` from each
+    /// of the `ends`, yielding at most `limit` alignments with `score`.
+    pub(crate) fn new(
+        b: &'a Matrix<Traceback>,
+        u: &'a [u8],
+        v: &'a [u8],
+        ends: Vec<(usize, usize)>,
+        score: i32,
+        limit: Option<usize>,
+    ) -> Self {
+        Self {
+            b,
+            u,
+            v,
+            ends: ends.into_iter(),
+            score,
+            stack: Vec::new(),
+            operations: Vec::new(),
+            remaining: limit,
+        }
+    }
+
+    /// Returns the alignment of the current path, that starts at the cell `(i, j)`.
+    fn alignment(&self, i: usize, j: usize) -> Alignment {
+        let operations = self.operations.iter().rev().copied().collect();
+
+        Alignment::new(self.u, self.v, i, j, operations, self.score)
+    }
+
+    /// Returns the next complete path found by the depth-first search.
+    fn next_path(&mut self) -> Option<Alignment> {
+        loop {
+            let (i, j, pending) = match self.stack.last_mut() {
+                Some(top) => top,
+                None => {
+                    let (i, j) = self.ends.next()?;
+
+                    if self.b[(i, j)].is_none() {
+                        return Some(self.alignment(i, j));
+                    }
+
+                    self.stack.push((i, j, self.b[(i, j)]));
+                    continue;
+                }
+            };
+
+            if pending.is_none() {
+                self.stack.pop();
+
+                // The end cell of the path has no operation leading to it.
+                if !self.stack.is_empty() {
+                    self.operations.pop();
+                }
+
+                continue;
+            }
+
+            let (i, j) = (*i, *j);
+
+            let next = pending.preferred();
+            pending.remove(next);
+
+            let (pi, pj, op) = if next == Traceback::DIAGONAL {
+                if self.u[i - 1] == self.v[j - 1] {
+                    (i - 1, j - 1, Operation::Match)
+                } else {
+                    (i - 1, j - 1, Operation::Mismatch)
+                }
+            } else if next == Traceback::UP {
+                (i - 1, j, Operation::Deletion)
+            } else {
+                (i, j - 1, Operation::Insertion)
+            };
+
+            self.operations.push(op);
+
+            if self.b[(pi, pj)].is_none() {
+                let alignment = self.alignment(pi, pj);
+                self.operations.pop();
+
+                return Some(alignment);
+            }
+
+            self.stack.push((pi, pj, self.b[(pi, pj)]));
+        }
+    }
+}
+
+impl Iterator for CoOptimalAlignments<'_> {
+    type Item = Alignment;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+
+        let alignment = self.next_path()?;
+
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+
+        Some(alignment)
+    }
+}
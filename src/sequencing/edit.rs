@@ -0,0 +1,341 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::math::matrix::Matrix;
+
+/// The edit distance metric, that is, the set of allowed edit operations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// Insertions, deletions and substitutions.
+    Levenshtein,
+
+    /// Insertions, deletions, substitutions and transpositions of adjacent
+    /// residues, in which no substring is edited more than once. Therefore,
+    /// the transposed residues are never edited again.
+    OptimalStringAlignment,
+
+    /// Insertions, deletions, substitutions and transpositions of adjacent
+    /// residues, without any restriction. Therefore, residues may be inserted
+    /// between or deleted from between the transposed residues.
+    Damerau,
+}
+
+/// An edit operation of an edit script.
+///
+/// The edit script transforms the sequence `u` into the sequence `v` reading
+/// both from the start, as the operations of an
+/// [`Alignment`](crate::sequencing::alignment::Alignment). Each operation but
+/// [`Edit::Match`] has a cost of one for each edited residue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edit {
+    /// A residue of `u` kept as it is in `v`.
+    Match,
+
+    /// A residue of `u` replaced by a different residue of `v`.
+    Substitution,
+
+    /// A residue of `v` inserted.
+    Insertion,
+
+    /// A residue of `u` deleted.
+    Deletion,
+
+    /// The residues `a` and `b` of `u[i..]` that are `deleted` residues apart,
+    /// swapped into the residues `b` and `a` of `v[j..]` that are `inserted`
+    /// residues apart. Both are zero for the transposition of adjacent residues.
+    Transposition { deleted: usize, inserted: usize },
+}
+
+impl Edit {
+    /// Returns the cost of the operation.
+    pub fn cost(&self) -> usize {
+        match *self {
+            Edit::Match => 0,
+            Edit::Substitution | Edit::Insertion | Edit::Deletion => 1,
+            Edit::Transposition { deleted, inserted } => 1 + deleted + inserted,
+        }
+    }
+}
+
+/// Returns the edit distance matrix between the sequences `u` and `v`, in which
+/// the cell `(i, j)` stores the distance between `u[..i]` and `v[..j]`.
+///
+/// The matrix is calculated using the Wagner-Fischer algorithm, extended to
+/// transpositions according to the specified [`Metric`]. The unrestricted
+/// Damerau distance is calculated using the Lowrance-Wagner algorithm, which
+/// for each cell only considers the transposition with the closest residues.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::edit::{edit_matrix, Metric};
+/// let d = edit_matrix("KITTEN", "SITTING", Metric::Levenshtein);
+///
+/// assert_eq!(d[(6, 7)], 3);
+/// ```
+pub fn edit_matrix<U, V>(u: U, v: V, metric: Metric) -> Matrix<usize>
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let mut d = Matrix::<usize>::new(u.len() + 1, v.len() + 1, 0);
+
+    for i in 1..d.row() {
+        d[(i, 0)] = i;
+    }
+
+    for j in 1..d.col() {
+        d[(0, j)] = j;
+    }
+
+    // It stores, for each residue, the last row in which it was found in `u`.
+    let mut last_row = [0usize; 256];
+
+    for i in 1..d.row() {
+        // It stores the last column of the current row in which `u[i - 1]`
+        // was found in `v`.
+        let mut last_col = 0;
+
+        for j in 1..d.col() {
+            let substitution = d[(i - 1, j - 1)] + usize::from(u[i - 1] != v[j - 1]);
+            let deletion = d[(i - 1, j)] + 1;
+            let insertion = d[(i, j - 1)] + 1;
+
+            let mut best = substitution.min(deletion).min(insertion);
+
+            let closest = (last_row[v[j - 1] as usize], last_col);
+
+            if let Some((cost, _, _)) = transposition(&d, u, v, (i, j), metric, closest) {
+                best = best.min(cost);
+            }
+
+            d[(i, j)] = best;
+
+            if u[i - 1] == v[j - 1] {
+                last_col = j;
+            }
+        }
+
+        last_row[u[i - 1] as usize] = i;
+    }
+
+    d
+}
+
+/// Returns the cost of the transposition ending in the cell `(i, j)` of the
+/// edit distance matrix `d`, together with the amount of residues deleted and
+/// inserted between the transposed residues, if there is such a transposition.
+///
+/// The `closest` stores the last row before `i` in which `v[j - 1]` is found in
+/// `u` and the last column before `j` in which `u[i - 1]` is found in `v`, or zero
+/// if there is none. It is only used by the [`Metric::Damerau`] distance.
+fn transposition(
+    d: &Matrix<usize>,
+    u: &[u8],
+    v: &[u8],
+    (i, j): (usize, usize),
+    metric: Metric,
+    closest: (usize, usize),
+) -> Option<(usize, usize, usize)> {
+    match metric {
+        Metric::Levenshtein => None,
+        Metric::OptimalStringAlignment => {
+            if i > 1 && j > 1 && u[i - 1] == v[j - 2] && u[i - 2] == v[j - 1] {
+                Some((d[(i - 2, j - 2)] + 1, 0, 0))
+            } else {
+                None
+            }
+        }
+        Metric::Damerau => {
+            let (k, l) = closest;
+
+            if k > 0 && l > 0 {
+                let (deleted, inserted) = (i - k - 1, j - l - 1);
+
+                Some((
+                    d[(k - 1, l - 1)] + 1 + deleted + inserted,
+                    deleted,
+                    inserted,
+                ))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Returns the edit script that transforms the sequence `u` into the sequence `v`.
+///
+/// Receiving the `d` matrix constructed by [`edit_matrix`] with the same `metric`
+/// and the two sequences, the optimal operations are backtracked from the last
+/// cell, preferring the match or substitution, then the deletion, the insertion
+/// and, finally, the transposition.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::edit::{edit_matrix, traceback_edit, Edit, Metric};
+/// let d = edit_matrix("ACGT", "CAGT", Metric::Damerau);
+///
+/// let script = traceback_edit(&d, "ACGT", "CAGT", Metric::Damerau);
+///
+/// assert_eq!(script[0], Edit::Transposition { deleted: 0, inserted: 0 });
+/// assert_eq!(script.iter().map(|e| e.cost()).sum::<usize>(), 1);
+/// ```
+pub fn traceback_edit<U, V>(d: &Matrix<usize>, u: U, v: V, metric: Metric) -> Vec<Edit>
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let mut script = Vec::with_capacity(d.row() + d.col());
+    let (mut i, mut j) = (d.row() - 1, d.col() - 1);
+
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && d[(i, j)] == d[(i - 1, j - 1)] + usize::from(u[i - 1] != v[j - 1]) {
+            script.push(if u[i - 1] == v[j - 1] {
+                Edit::Match
+            } else {
+                Edit::Substitution
+            });
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && d[(i, j)] == d[(i - 1, j)] + 1 {
+            script.push(Edit::Deletion);
+            i -= 1;
+        } else if j > 0 && d[(i, j)] == d[(i, j - 1)] + 1 {
+            script.push(Edit::Insertion);
+            j -= 1;
+        } else {
+            // It recovers the closest transposed residues, which are the ones
+            // used by `edit_matrix`, since they are not stored.
+            let k = (1..i).rev().find(|&k| u[k - 1] == v[j - 1]).unwrap_or(0);
+            let l = (1..j).rev().find(|&l| v[l - 1] == u[i - 1]).unwrap_or(0);
+
+            let (_, deleted, inserted) = transposition(d, u, v, (i, j), metric, (k, l))
+                .expect("The cell must have an optimal predecessor");
+
+            script.push(Edit::Transposition { deleted, inserted });
+            i -= deleted + 2;
+            j -= inserted + 2;
+        }
+    }
+
+    script.reverse();
+    script
+}
+
+/// Returns the Levenshtein distance between the sequences `u` and `v`.
+///
+/// The Levenshtein distance indicates the minimum number of insertions,
+/// deletions and substitutions required to change a sequence into the other.
+/// Unlike the [Hamming distance](crate::sequencing::hamming), the sequences
+/// may have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::edit::levenshtein;
+/// assert_eq!(levenshtein("KITTEN", "SITTING"), 3);
+/// ```
+pub fn levenshtein<U, V>(u: U, v: V) -> usize
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    edit_matrix(u, v, Metric::Levenshtein)[(u.len(), v.len())]
+}
+
+/// Returns the optimal string alignment distance between the sequences `u` and `v`.
+///
+/// It is the Levenshtein distance allowing, as well, the transposition of two
+/// adjacent residues, as long as no substring is edited more than once.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::edit::osa;
+/// assert_eq!(osa("CA", "AC"), 1);
+/// assert_eq!(osa("CA", "ABC"), 3);
+/// ```
+pub fn osa<U, V>(u: U, v: V) -> usize
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    edit_matrix(u, v, Metric::OptimalStringAlignment)[(u.len(), v.len())]
+}
+
+/// Returns the Damerau-Levenshtein distance between the sequences `u` and `v`.
+///
+/// It is the Levenshtein distance allowing, as well, the transposition of two
+/// adjacent residues, without restricting the edits of the transposed residues.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::edit::damerau;
+/// assert_eq!(damerau("CA", "AC"), 1);
+/// assert_eq!(damerau("CA", "ABC"), 2);
+/// ```
+pub fn damerau<U, V>(u: U, v: V) -> usize
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    edit_matrix(u, v, Metric::Damerau)[(u.len(), v.len())]
+}
+
+/// Returns the edit distance between the sequences `u` and `v` using the specified
+/// [`Metric`], together with the edit script that transforms `u` into `v`.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::edit::{edit_distance, Edit, Metric};
+/// let (distance, script) = edit_distance("KITTEN", "SITTING", Metric::Levenshtein);
+///
+/// assert_eq!(distance, 3);
+/// assert_eq!(script[0], Edit::Substitution);
+/// assert_eq!(script[6], Edit::Insertion);
+/// ```
+pub fn edit_distance<U, V>(u: U, v: V, metric: Metric) -> (usize, Vec<Edit>)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let d = edit_matrix(u, v, metric);
+
+    (d[(u.len(), v.len())], traceback_edit(&d, u, v, metric))
+}
@@ -1,4 +1,12 @@
 use crate::math::matrix::Matrix;
+use crate::sequencing::alignment::{Alignment, Operation};
+use crate::sequencing::cooptimal::CoOptimalAlignments;
+use crate::sequencing::scoring::{max_residue_score, residue_score, NEG_INF};
+use crate::sequencing::sequence::{align_stranded, Strand};
+use crate::sequencing::substitution::SubstitutionMatrix;
+use crate::sequencing::traceback::Traceback;
+
+pub use crate::sequencing::scoring::AffineTraceback;
 
 /// Global Alignment Options.
 ///
@@ -8,26 +16,127 @@ use crate::math::matrix::Matrix;
 /// # Examples
 ///
 /// ```
+/// # use bioinformatics::sequencing::global::Options;
 /// let opt = Options {
 ///     match_: 5,
 ///     mismatch: -3,
 ///     gap: -4,
+///     substitution: None,
 /// };
 /// ```
 ///
 /// The code snippet above defines a function with match point equals to `5`,
 /// mismatch point equals to `-3` and gap point equals to `-4`.
+///
+/// # Substitution Matrices
+///
+/// If a [`SubstitutionMatrix`] is specified, then it is used to score the
+/// aligned residues instead of the `match` and `mismatch` points.
+///
+/// ```
+/// # use bioinformatics::sequencing::global::Options;
+/// # use bioinformatics::sequencing::substitution::SubstitutionMatrix;
+/// let opt = Options {
+///     match_: 0,
+///     mismatch: 0,
+///     gap: -8,
+///     substitution: Some(SubstitutionMatrix::blosum62()),
+/// };
+///
+/// assert_eq!(opt.score(b'I', b'L'), 2);
+/// ```
 pub struct Options {
     pub match_: i8,
     pub mismatch: i8,
     pub gap: i8,
+    pub substitution: Option<SubstitutionMatrix>,
+}
+
+impl Options {
+    /// Returns the score of aligning the residue `a` against the residue `b`.
+    pub fn score(&self, a: u8, b: u8) -> i32 {
+        residue_score(self.substitution.as_ref(), self.match_, self.mismatch, a, b)
+    }
+
+    /// Returns the highest score of aligning two residues.
+    pub fn max_score(&self) -> i32 {
+        max_residue_score(self.substitution.as_ref(), self.match_, self.mismatch)
+    }
+}
+
+/// Global Alignment Mode.
+///
+/// It specifies which ends of the sequences may be left unaligned without being
+/// penalized by the gap point, that is, the `free end gaps`. Each mode is a variant
+/// of the Needleman-Wunsch algorithm that only differs in how the first row and
+/// column are initialized and in which cell the traceback starts.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::Mode;
+/// let mode = Mode::FreeEndGaps {
+///     u_start: true,
+///     u_end: false,
+///     v_start: false,
+///     v_end: true,
+/// };
+///
+/// assert_eq!(mode.free_end_gaps(), Mode::Overlap.free_end_gaps());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Both sequences are entirely aligned, hence no end gap is free.
+    Global,
+
+    /// Every end gap is free, hence any prefix and suffix of both sequences
+    /// may be left unaligned.
+    SemiGlobal,
+
+    /// The sequence `u` is entirely aligned inside the sequence `v`, hence the
+    /// start and the end of `v` may be left unaligned. It is used, for instance,
+    /// to map a read `u` against a reference `v`.
+    Fitting,
+
+    /// A suffix of `u` is aligned against a prefix of `v`, hence the start of `u`
+    /// and the end of `v` may be left unaligned. It is used, for instance, to
+    /// find the overlap between two reads in assembly.
+    Overlap,
+
+    /// The free end gaps are specified for each sequence end.
+    FreeEndGaps {
+        u_start: bool,
+        u_end: bool,
+        v_start: bool,
+        v_end: bool,
+    },
+}
+
+impl Mode {
+    /// Returns whether the `(u_start, u_end, v_start, v_end)` ends of the
+    /// sequences may be left unaligned without being penalized.
+    pub fn free_end_gaps(&self) -> (bool, bool, bool, bool) {
+        match *self {
+            Mode::Global => (false, false, false, false),
+            Mode::SemiGlobal => (true, true, true, true),
+            Mode::Fitting => (false, false, true, true),
+            Mode::Overlap => (true, false, false, true),
+            Mode::FreeEndGaps {
+                u_start,
+                u_end,
+                v_start,
+                v_end,
+            } => (u_start, u_end, v_start, v_end),
+        }
+    }
 }
 
 /// Align two sequences globally.
 ///
 /// Further, a tuple is returned containing in the first component the constructed
-/// `matrix` used to calculate the alignment score and the second component that is
-/// used by the alignment printers that contains the `arrow`s.
+/// `matrix` used to calculate the alignment score and the second component the
+/// [`Traceback`] matrix used to backtrack the alignment, in which every optimal
+/// predecessor of each cell is stored.
 ///
 /// It applies the global alignment between the two specified sequences using the
 /// Needleman-Wunsch algorithm.
@@ -39,57 +148,106 @@ pub struct Options {
 /// # Examples
 ///
 /// ```
-/// let (s, b) = align_global("AAAGATACCA", "GGGACTTAGA", &Options { match_: 5, mismatch: -3, gap: -4 } );
+/// # use bioinformatics::sequencing::global::{align_global, print_align_global, Options};
+/// let (s, b) = align_global("AAAGATACCA", "GGGACTTAGA", &Options { match_: 5, mismatch: -3, gap: -4, substitution: None } );
 ///
-/// print_align_global(b, "AAAGATACCA", "GGGACTTAGA");
+/// print_align_global(&s, &b, "AAAGATACCA", "GGGACTTAGA");
 /// ```
 ///
 /// The code snippet above performs the global alignment between the two specified sequences
 /// being them `AAAGATACCA` and `GGGACTTAGA` and prints the global alignment.
 ///
 /// ```text
-/// AAGA--TACCA
-/// GGGACTTA-GA
+/// AAAGA--TACCA
+/// -GGGACTTA-GA
+/// ```
+pub fn align_global<U, V>(u: U, v: V, opt: &Options) -> (Matrix<i32>, Matrix<Traceback>)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    align_global_mode(u, v, opt, Mode::Global)
+}
+
+/// Align two sequences using the specified [`Mode`].
+///
+/// Further, a tuple is returned containing in the first component the constructed
+/// `matrix` used to calculate the alignment score and the second component the
+/// [`Traceback`] matrix used to backtrack the alignment.
+///
+/// The first row and column are initialized with zeros, instead of the multiples of
+/// the gap, for the sequence starts that may be left unaligned. Moreover, the cells
+/// in which a free start gap ends have no predecessor, hence the traceback stops on them.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{align_global_mode, traceback_global_mode, Mode, Options};
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let (s, b) = align_global_mode("GATTA", "CCCGATTACCC", &opt, Mode::Fitting);
+///
+/// let alignment = traceback_global_mode(&s, &b, "GATTA", "CCCGATTACCC", Mode::Fitting);
+///
+/// assert_eq!(alignment.score, 25);
+/// assert_eq!((alignment.v_start, alignment.v_end), (3, 8));
 /// ```
-pub fn align_global(u: &str, v: &str, opt: &Options) -> (Matrix<i32>, Matrix<char>) {
+///
+/// The code snippet above performs the fitting alignment of `GATTA` inside `CCCGATTACCC`,
+/// in which the unaligned `CCC` at both ends of the second sequence are not penalized.
+pub fn align_global_mode<U, V>(
+    u: U,
+    v: V,
+    opt: &Options,
+    mode: Mode,
+) -> (Matrix<i32>, Matrix<Traceback>)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
     let mut s = Matrix::<i32>::new(u.len() + 1, v.len() + 1, 0i32);
-    let mut b = Matrix::<char>::new(u.len() + 1, v.len() + 1, ' ');
+    let mut b = Matrix::<Traceback>::new(u.len() + 1, v.len() + 1, Traceback::NONE);
+
+    let (u_start, _u_end, v_start, _v_end) = mode.free_end_gaps();
 
-    // It initializes the first row with the multiples of the gap.
-    for i in 0..s.row() {
-        s[i][0] = (i as i32) * (opt.gap as i32);
+    // It initializes the first column with the multiples of the gap, unless
+    // the start of `u` may be left unaligned.
+    if !u_start {
+        for i in 1..s.row() {
+            s[(i, 0)] = (i as i32) * (opt.gap as i32);
+            b[(i, 0)] = Traceback::UP;
+        }
     }
 
-    // It initializes the first column with the multiple of the gap.
-    for j in 1..s.col() {
-        s[0][j] = (j as i32) * (opt.gap as i32);
+    // It initializes the first row with the multiples of the gap, unless
+    // the start of `v` may be left unaligned.
+    if !v_start {
+        for j in 1..s.col() {
+            s[(0, j)] = (j as i32) * (opt.gap as i32);
+            b[(0, j)] = Traceback::LEFT;
+        }
     }
 
-    let u_chars = u.chars().collect::<Vec<char>>();
-    let v_chars = v.chars().collect::<Vec<char>>();
+    let gap = opt.gap as i32;
 
     // It calculates the matrix values using the previously specified
-    // score function (match, mismatch and gap).
-    for i in 1..s.row() {
-        for j in 1..s.col() {
-            let diagonal = s[i - 1][j - 1]
-                + (if u_chars[i - 1] == v_chars[j - 1] {
-                    opt.match_ as i32
-                } else {
-                    opt.mismatch as i32
-                });
-            let upper = s[i - 1][j] + (opt.gap as i32);
-            let left = s[i][j - 1] + (opt.gap as i32);
-
-            if diagonal >= upper && diagonal >= left {
-                s[i][j] = diagonal;
-                b[i][j] = 'd';
-            } else if diagonal < upper && upper >= left {
-                s[i][j] = upper;
-                b[i][j] = 'u';
-            } else {
-                s[i][j] = left;
-                b[i][j] = 'l';
+    // score function (match, mismatch or substitution matrix, and gap),
+    // without checking the bounds of the cells in the inner loop.
+    for (i, &a) in (1..s.row()).zip(u.iter()) {
+        for (j, &c) in (1..s.col()).zip(v.iter()) {
+            // SAFETY: `1 <= i < s.row()` and `1 <= j < s.col()`, hence the cell
+            // `(i, j)` and its upper, left and upper left neighbours are inside
+            // `s`, and `b` has the same size as `s`.
+            unsafe {
+                let diagonal = *s.get_unchecked(i - 1, j - 1) + opt.score(a, c);
+                let upper = *s.get_unchecked(i - 1, j) + gap;
+                let left = *s.get_unchecked(i, j - 1) + gap;
+
+                let best = diagonal.max(upper).max(left);
+
+                *s.get_unchecked_mut(i, j) = best;
+                *b.get_unchecked_mut(i, j) = Traceback::from_scores(best, diagonal, upper, left);
             }
         }
     }
@@ -97,79 +255,742 @@ pub fn align_global(u: &str, v: &str, opt: &Options) -> (Matrix<i32>, Matrix<cha
     (s, b)
 }
 
+/// Returns the global alignment between the sequences `u` and `v`.
+///
+/// Receiving the `s` that represents the constructed `matrix` to calculate
+/// the alignment score, the `b` that represents the constructed [`Traceback`]
+/// matrix and the two sequences being aligned, the preferred predecessors
+/// (diagonal, then up, then left) are backtracked from the last cell to
+/// construct the [`Alignment`].
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{align_global, traceback_global, Options};
+/// let (s, b) = align_global("AAAGATACCA", "GGGACTTAGA", &Options { match_: 5, mismatch: -3, gap: -4, substitution: None } );
+///
+/// let alignment = traceback_global(&s, &b, "AAAGATACCA", "GGGACTTAGA");
+///
+/// assert_eq!(alignment.aligned_u, "AAAGA--TACCA");
+/// assert_eq!(alignment.aligned_v, "-GGGACTTA-GA");
+/// assert_eq!(alignment.score, 0);
+/// ```
+pub fn traceback_global<U, V>(s: &Matrix<i32>, b: &Matrix<Traceback>, u: U, v: V) -> Alignment
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    traceback_global_mode(s, b, u, v, Mode::Global)
+}
+
+/// Returns the alignment between the sequences `u` and `v` using the specified [`Mode`].
+///
+/// Receiving the `s` and `b` matrices constructed by [`align_global_mode`] with the
+/// same `mode` and the two sequences being aligned, the preferred predecessors are
+/// backtracked to construct the [`Alignment`].
+///
+/// The traceback starts at the last cell, unless the end of a sequence may be left
+/// unaligned. In that case, it starts at the cell with the maximum score in the last
+/// column (end of `u` free) or in the last row (end of `v` free). The free end gaps
+/// are not part of the alignment, that is, they are only reflected in its coordinates.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{align_global_mode, traceback_global_mode, Mode, Options};
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let (s, b) = align_global_mode("TTTTACGTA", "ACGTAGGGG", &opt, Mode::Overlap);
+///
+/// let alignment = traceback_global_mode(&s, &b, "TTTTACGTA", "ACGTAGGGG", Mode::Overlap);
+///
+/// assert_eq!(alignment.aligned_u, "ACGTA");
+/// assert_eq!((alignment.u_start, alignment.u_end), (4, 9));
+/// assert_eq!((alignment.v_start, alignment.v_end), (0, 5));
+/// ```
+pub fn traceback_global_mode<U, V>(
+    s: &Matrix<i32>,
+    b: &Matrix<Traceback>,
+    u: U,
+    v: V,
+    mode: Mode,
+) -> Alignment
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let (_u_start, u_end, _v_start, v_end) = mode.free_end_gaps();
+
+    let mut i = s.row() - 1;
+    let mut j = s.col() - 1;
+
+    // It looks for a better cell to start the traceback in the last column,
+    // since the end of `u` may be left unaligned.
+    if u_end {
+        for k in 0..s.row() {
+            if s[(k, s.col() - 1)] > s[(i, j)] {
+                i = k;
+                j = s.col() - 1;
+            }
+        }
+    }
+
+    // It looks for a better cell to start the traceback in the last row,
+    // since the end of `v` may be left unaligned.
+    if v_end {
+        for k in 0..s.col() {
+            if s[(s.row() - 1, k)] > s[(i, j)] {
+                i = s.row() - 1;
+                j = k;
+            }
+        }
+    }
+
+    Alignment::from_traceback(b, u, v, i, j, s[(i, j)])
+}
+
 /// Prints the global alignment between the sequences `u` and `v`.
 ///
-/// Receiveing the `b` that represents the matrix containing the
-/// previously constructed `arrow` matrix and the two sequences
-/// being aligned, the global alignment is printed to the standard
-/// output.
+/// Receiving the `s` that represents the constructed `matrix` to calculate
+/// the alignment score, the `b` that represents the constructed [`Traceback`]
+/// matrix and the two sequences being aligned, the global alignment is
+/// printed to the standard output.
 ///
 /// # Examples
 ///
 /// ```
-/// let (s, b) = align_global("AAAGATACCA", "GGGACTTAGA", &Options { match_: 5, mismatch: -3, gap: -4 } );
+/// # use bioinformatics::sequencing::global::{align_global, print_align_global, Options};
+/// let (s, b) = align_global("AAAGATACCA", "GGGACTTAGA", &Options { match_: 5, mismatch: -3, gap: -4, substitution: None } );
 ///
-/// print_align_global(b, "AAAGATACCA", "GGGACTTAGA");
+/// print_align_global(&s, &b, "AAAGATACCA", "GGGACTTAGA");
 /// ```
 ///
 /// The code snippet above performs the global alignment between the two specified sequences
 /// being them `AAAGATACCA` and `GGGACTTAGA` and prints the global alignment.
 ///
 /// ```text
-/// AAGA--TACCA
-/// GGGACTTA-GA
-/// ```
-pub fn print_align_global(b: &Matrix<char>, u: &str, v: &str) {
-    let mut aligned_u = String::from("");
-    let mut aligned_v = String::from("");
-
-    print_align_global_rec(
-        b,
-        u,
-        v,
-        &mut aligned_u,
-        &mut aligned_v,
-        b.row() - 1,
-        b.col() - 1,
-    );
-
-    println!("{}", aligned_u.chars().rev().collect::<String>());
-    println!("{}", aligned_v.chars().rev().collect::<String>());
-}
-
-/// Prints the global alignment between two sequences `u` and `v`.
-///
-/// This function performs the printing of the global alignment recursively.
-/// Moreover, this function is set `private` because it requests much arguments
-/// to perform the printing and some of its arguments are not needed to be
-/// initialized by the library users.
-///
-/// Therefore, the function [`print_align_global`] is used to provide an easier
-/// way to print the global alignment between two sequences.
-fn print_align_global_rec(
-    b: &Matrix<char>,
-    u: &str,
-    v: &str,
-    aligned_u: &mut String,
-    aligned_v: &mut String,
-    i: usize,
-    j: usize,
-) {
-    if i == 0 || j == 0 {
+/// AAAGA--TACCA
+/// -GGGACTTA-GA
+/// ```
+pub fn print_align_global<U, V>(s: &Matrix<i32>, b: &Matrix<Traceback>, u: U, v: V)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    println!("{}", traceback_global(s, b, u, v));
+}
+
+/// Returns the score of the global alignment between the sequences `u` and `v`.
+///
+/// Further, a tuple is returned containing in the first component the optimal score
+/// and in the second and third components the end coordinates of the alignment in
+/// `u` and `v`, which for a global alignment are always the sequence lengths.
+///
+/// Unlike [`align_global`], neither the score matrix nor the [`Traceback`] matrix is
+/// built, since only two rows are kept at a time. Therefore, it runs in linear memory.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{score_global, Options};
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+///
+/// assert_eq!(score_global("AAAGATACCA", "GGGACTTAGA", &opt), (0, 10, 10));
+/// ```
+pub fn score_global<U, V>(u: U, v: V, opt: &Options) -> (i32, usize, usize)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    score_global_mode(u, v, opt, Mode::Global)
+}
+
+/// Returns the score of the alignment between the sequences `u` and `v` using the
+/// specified [`Mode`].
+///
+/// Further, a tuple is returned containing in the first component the optimal score
+/// and in the second and third components the end coordinates of the alignment in
+/// `u` and `v`. The end cell is chosen as in [`traceback_global_mode`], hence the
+/// coordinates are the same as those of the backtracked [`Alignment`].
+///
+/// Only two rows and the last column of the score matrix are kept, so it runs in
+/// linear memory.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{score_global_mode, Mode, Options};
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+///
+/// assert_eq!(score_global_mode("GATTA", "CCCGATTACCC", &opt, Mode::Fitting), (25, 5, 8));
+/// ```
+pub fn score_global_mode<U, V>(u: U, v: V, opt: &Options, mode: Mode) -> (i32, usize, usize)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let (u_start, u_end, v_start, v_end) = mode.free_end_gaps();
+    let gap = opt.gap as i32;
+
+    // It initializes the first row as in `align_global_mode`.
+    let mut previous = (0..=v.len())
+        .map(|j| if v_start { 0 } else { (j as i32) * gap })
+        .collect::<Vec<i32>>();
+    let mut current = vec![0i32; v.len() + 1];
+
+    // It stores the last column, since the end of `u` may be left unaligned.
+    let mut last_column = Vec::with_capacity(u.len() + 1);
+    last_column.push(previous[v.len()]);
+
+    for (i, &a) in u.iter().enumerate() {
+        current[0] = if u_start { 0 } else { ((i + 1) as i32) * gap };
+
+        for (j, &b) in v.iter().enumerate() {
+            let diagonal = previous[j] + opt.score(a, b);
+            let upper = previous[j + 1] + gap;
+            let left = current[j] + gap;
+
+            current[j + 1] = diagonal.max(upper).max(left);
+        }
+
+        last_column.push(current[v.len()]);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let (mut i, mut j) = (u.len(), v.len());
+    let mut best = previous[j];
+
+    if u_end {
+        for (k, &score) in last_column.iter().enumerate() {
+            if score > best {
+                (best, i, j) = (score, k, v.len());
+            }
+        }
+    }
+
+    if v_end {
+        for (k, &score) in previous.iter().enumerate() {
+            if score > best {
+                (best, i, j) = (score, u.len(), k);
+            }
+        }
+    }
+
+    (best, i, j)
+}
+
+/// Align the sequence `u` in both orientations against `v` using the specified [`Mode`].
+///
+/// Since the strand of a read is usually unknown, the read `u` is aligned as it is and
+/// as its [reverse complement](crate::sequencing::sequence::reverse_complement), and
+/// the alignment with the highest score is returned together with its [`Strand`]. If
+/// both scores are equal, then the forward strand is chosen.
+///
+/// The coordinates of `u` in a [`Strand::Reverse`] alignment refer to the reverse
+/// complement of `u`, that is also the sequence shown in `aligned_u`, while the
+/// coordinates of `v` are not affected.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{align_global_stranded, Mode, Options};
+/// # use bioinformatics::sequencing::sequence::Strand;
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let (alignment, strand) = align_global_stranded("CATAATGGT", "GGGGACCATTATGGGGG", &opt, Mode::Fitting);
+///
+/// assert_eq!(strand, Strand::Reverse);
+/// assert_eq!(alignment.aligned_u, "ACCATTATG");
+/// assert_eq!((alignment.v_start, alignment.v_end), (4, 13));
+/// ```
+pub fn align_global_stranded<U, V>(u: U, v: V, opt: &Options, mode: Mode) -> (Alignment, Strand)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let v = v.as_ref();
+
+    align_stranded(u.as_ref(), |u| {
+        let (s, b) = align_global_mode(u, v, opt, mode);
+        traceback_global_mode(&s, &b, u, v, mode)
+    })
+}
+
+/// Returns an iterator over every co-optimal global alignment between the
+/// sequences `u` and `v`.
+///
+/// Receiving the `s` and `b` matrices constructed by [`align_global`], that keeps
+/// every optimal predecessor of each cell, and the two sequences being aligned,
+/// every path of optimal predecessors from the last cell to the first one is
+/// backtracked. If a `limit` is specified, then at most `limit` alignments are
+/// yielded.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{align_global, traceback_global_cooptimal, Options};
+/// let opt = Options { match_: 1, mismatch: -1, gap: -1, substitution: None };
+/// let (s, b) = align_global("AAT", "AT", &opt);
+///
+/// let alignments = traceback_global_cooptimal(&s, &b, "AAT", "AT", None)
+///     .map(|alignment| alignment.aligned_v)
+///     .collect::<Vec<String>>();
+///
+/// assert_eq!(alignments, vec!["-AT", "A-T"]);
+/// ```
+pub fn traceback_global_cooptimal<'a, U, V>(
+    s: &Matrix<i32>,
+    b: &'a Matrix<Traceback>,
+    u: &'a U,
+    v: &'a V,
+    limit: Option<usize>,
+) -> CoOptimalAlignments<'a>
+where
+    U: AsRef<[u8]> + ?Sized,
+    V: AsRef<[u8]> + ?Sized,
+{
+    let (i, j) = (s.row() - 1, s.col() - 1);
+
+    CoOptimalAlignments::new(b, u.as_ref(), v.as_ref(), vec![(i, j)], s[(i, j)], limit)
+}
+
+/// Align two sequences globally in linear space.
+///
+/// It applies the global alignment between the two specified sequences using the
+/// Hirschberg algorithm, that is, a divide-and-conquer variant of the Needleman-Wunsch
+/// algorithm that only keeps two rows of the score matrix at a time. Therefore, it
+/// requires `O(n + m)` memory instead of the `O(n * m)` required by [`align_global`],
+/// at the cost of roughly doubling the running time.
+///
+/// The returned [`Alignment`] has the same optimal score as the one produced by
+/// [`align_global`], although a different alignment may be chosen among the ones
+/// with the same optimal score.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{align_global_hirschberg, Options};
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let alignment = align_global_hirschberg("AAAGATACCA", "GGGACTTAGA", &opt);
+///
+/// assert_eq!(alignment.score, 0);
+/// ```
+pub fn align_global_hirschberg<U, V>(u: U, v: V, opt: &Options) -> Alignment
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let mut operations = Vec::with_capacity(u.len() + v.len());
+
+    hirschberg_rec(u, v, opt, &mut operations);
+
+    // It calculates the alignment score from the operations, since the
+    // score matrix is never entirely stored.
+    let mut score = 0i32;
+    let (mut i, mut j) = (0usize, 0usize);
+
+    for op in operations.iter() {
+        match op {
+            Operation::Match | Operation::Mismatch => {
+                score += opt.score(u[i], v[j]);
+                i += 1;
+                j += 1;
+            }
+            Operation::Insertion => {
+                score += opt.gap as i32;
+                j += 1;
+            }
+            Operation::Deletion => {
+                score += opt.gap as i32;
+                i += 1;
+            }
+        }
+    }
+
+    Alignment::new(u, v, 0, 0, operations, score)
+}
+
+/// Appends to `operations` the global alignment between `u` and `v`.
+///
+/// The sequence `u` is split in half and the column in which the optimal
+/// alignment crosses the middle row is found by combining the last row of the
+/// forward scores of the upper half with the last row of the backward scores
+/// of the lower half. Then, both halves are aligned recursively.
+fn hirschberg_rec(u: &[u8], v: &[u8], opt: &Options, operations: &mut Vec<Operation>) {
+    if u.is_empty() {
+        operations.extend(v.iter().map(|_| Operation::Insertion));
         return;
     }
 
-    if b[i][j] == 'd' {
-        *aligned_u += &u.chars().nth(i - 1).unwrap().to_string();
-        *aligned_v += &v.chars().nth(j - 1).unwrap().to_string();
-        print_align_global_rec(b, u, v, aligned_u, aligned_v, i - 1, j - 1);
-    } else if b[i][j] == 'u' {
-        *aligned_u += &u.chars().nth(i - 1).unwrap().to_string();
-        *aligned_v += "-";
-        print_align_global_rec(b, u, v, aligned_u, aligned_v, i - 1, j);
-    } else {
-        *aligned_u += "-";
-        *aligned_v += &v.chars().nth(j - 1).unwrap().to_string();
-        print_align_global_rec(b, u, v, aligned_u, aligned_v, i, j - 1);
+    if v.is_empty() {
+        operations.extend(u.iter().map(|_| Operation::Deletion));
+        return;
+    }
+
+    // If one of the sequences has a single residue, then the Needleman-Wunsch
+    // algorithm already runs in linear space.
+    if u.len() == 1 || v.len() == 1 {
+        let (s, b) = align_global(u, v, opt);
+
+        operations.extend(traceback_global(&s, &b, u, v).operations);
+        return;
+    }
+
+    let mid = u.len() / 2;
+
+    // It finds the column `k` that maximizes the score of aligning the
+    // upper half with `v[..k]` and the lower half with `v[k..]`. The rows
+    // are dropped before recursing to keep the memory linear.
+    let k = {
+        let upper = last_row(u[..mid].iter(), v.iter(), v.len(), opt);
+        let lower = last_row(u[mid..].iter().rev(), v.iter().rev(), v.len(), opt);
+
+        (0..=v.len())
+            .rev()
+            .max_by_key(|&j| upper[j] + lower[v.len() - j])
+            .unwrap()
+    };
+
+    hirschberg_rec(&u[..mid], &v[..k], opt, operations);
+    hirschberg_rec(&u[mid..], &v[k..], opt, operations);
+}
+
+/// Returns the last row of the Needleman-Wunsch score matrix between `u`
+/// and `v`, keeping only two rows at a time.
+fn last_row<'a, U, V>(u: U, v: V, v_len: usize, opt: &Options) -> Vec<i32>
+where
+    U: Iterator<Item = &'a u8>,
+    V: Iterator<Item = &'a u8> + Clone,
+{
+    let gap = opt.gap as i32;
+
+    let mut prev = (0..=v_len).map(|j| (j as i32) * gap).collect::<Vec<i32>>();
+    let mut curr = vec![0i32; v_len + 1];
+
+    for &a in u {
+        curr[0] = prev[0] + gap;
+
+        for (j, &b) in v.clone().enumerate() {
+            let diagonal = prev[j] + opt.score(a, b);
+            let upper = prev[j + 1] + gap;
+            let left = curr[j] + gap;
+
+            curr[j + 1] = diagonal.max(upper).max(left);
+        }
+
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev
+}
+
+/// Global Alignment Affine Gap Options.
+///
+/// This structure provides a manner to specify the `score` function that
+/// comprises the `match` point, `mismatch` point, `gap open` point and the
+/// `gap extend` point.
+///
+/// The first position of a gap is scored with `gap_open`, while each of its
+/// following positions is scored with `gap_extend`. Therefore, a gap with
+/// length `k` is scored as `gap_open + (k - 1) * gap_extend`.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::AffineOptions;
+/// let opt = AffineOptions {
+///     match_: 5,
+///     mismatch: -3,
+///     gap_open: -10,
+///     gap_extend: -1,
+///     substitution: None,
+/// };
+/// ```
+///
+/// The code snippet above defines a function with match point equals to `5`,
+/// mismatch point equals to `-3`, gap open point equals to `-10` and gap extend
+/// point equals to `-1`.
+///
+/// As in [`Options`], if a [`SubstitutionMatrix`] is specified, then it is used
+/// to score the aligned residues instead of the `match` and `mismatch` points.
+pub struct AffineOptions {
+    pub match_: i8,
+    pub mismatch: i8,
+    pub gap_open: i8,
+    pub gap_extend: i8,
+    pub substitution: Option<SubstitutionMatrix>,
+}
+
+impl AffineOptions {
+    /// Returns the score of aligning the residue `a` against the residue `b`.
+    pub fn score(&self, a: u8, b: u8) -> i32 {
+        residue_score(self.substitution.as_ref(), self.match_, self.mismatch, a, b)
     }
 }
+
+/// Align two sequences globally using affine gap points.
+///
+/// Further, a tuple is returned containing in the first component the constructed
+/// `matrix` used to calculate the alignment score and the second component the
+/// [`AffineTraceback`] used to backtrack the alignment.
+///
+/// It applies the global alignment between the two specified sequences using the
+/// Gotoh algorithm, that is, the Needleman-Wunsch algorithm extended with three
+/// matrices to distinguish opening a gap from extending it.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{align_global_affine, print_align_global_affine, AffineOptions};
+/// let opt = AffineOptions { match_: 5, mismatch: -3, gap_open: -10, gap_extend: -1, substitution: None };
+/// let (s, b) = align_global_affine("ACGTTTTACG", "ACGACG", &opt);
+///
+/// assert_eq!(s[s.row() - 1][s.col() - 1], 17);
+///
+/// print_align_global_affine(&s, &b, "ACGTTTTACG", "ACGACG");
+/// ```
+///
+/// The code snippet above performs the global alignment between the two specified sequences
+/// being them `ACGTTTTACG` and `ACGACG` and prints the global alignment, in which the gap
+/// is kept in a single block.
+///
+/// ```text
+/// ACGTTTTACG
+/// ACG----ACG
+/// ```
+pub fn align_global_affine<U, V>(u: U, v: V, opt: &AffineOptions) -> (Matrix<i32>, AffineTraceback)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let mut s = Matrix::<i32>::new(u.len() + 1, v.len() + 1, 0i32);
+    let mut tb = AffineTraceback {
+        b: Matrix::<Traceback>::new(u.len() + 1, v.len() + 1, Traceback::NONE),
+        up_extend: Matrix::<bool>::new(u.len() + 1, v.len() + 1, false),
+        left_extend: Matrix::<bool>::new(u.len() + 1, v.len() + 1, false),
+    };
+
+    let gap_open = opt.gap_open as i32;
+    let gap_extend = opt.gap_extend as i32;
+
+    // It initializes the first column with a single gap.
+    for i in 1..s.row() {
+        s[(i, 0)] = gap_open + ((i - 1) as i32) * gap_extend;
+        tb.b[(i, 0)] = Traceback::UP;
+        tb.up_extend[(i, 0)] = i > 1;
+    }
+
+    // It initializes the first row with a single gap.
+    for j in 1..s.col() {
+        s[(0, j)] = gap_open + ((j - 1) as i32) * gap_extend;
+        tb.b[(0, j)] = Traceback::LEFT;
+        tb.left_extend[(0, j)] = j > 1;
+    }
+
+    // It stores the best score of the alignments ending with an upper gap
+    // in the previous row. Only the previous row is needed, since the upper
+    // gaps are extended from the cell right above.
+    let mut upper_gap = vec![NEG_INF; s.col()];
+
+    // It calculates the matrices values using the previously specified
+    // score function (match, mismatch or substitution matrix, gap open
+    // and gap extend).
+    for i in 1..s.row() {
+        // It stores the best score of the alignments ending with a left gap
+        // in the current row.
+        let mut left_gap = NEG_INF;
+
+        for j in 1..s.col() {
+            let diagonal = s[(i - 1, j - 1)] + opt.score(u[i - 1], v[j - 1]);
+
+            let upper_open = s[(i - 1, j)] + gap_open;
+            let upper_extend = upper_gap[j] + gap_extend;
+            tb.up_extend[(i, j)] = upper_extend > upper_open;
+            upper_gap[j] = upper_open.max(upper_extend);
+
+            let left_open = s[(i, j - 1)] + gap_open;
+            let left_extend = left_gap + gap_extend;
+            tb.left_extend[(i, j)] = left_extend > left_open;
+            left_gap = left_open.max(left_extend);
+
+            let upper = upper_gap[j];
+            let left = left_gap;
+
+            let best = diagonal.max(upper).max(left);
+
+            s[(i, j)] = best;
+            tb.b[(i, j)] = Traceback::from_scores(best, diagonal, upper, left);
+        }
+    }
+
+    (s, tb)
+}
+
+/// Returns the affine gap global alignment between the sequences `u` and `v`.
+///
+/// Receiving the `s` that represents the constructed `matrix` to calculate the
+/// alignment score, the `tb` that represents the previously constructed
+/// [`AffineTraceback`] and the two sequences being aligned, the gap states are
+/// backtracked from the last cell to construct the [`Alignment`].
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{align_global_affine, traceback_global_affine, AffineOptions};
+/// let opt = AffineOptions { match_: 5, mismatch: -3, gap_open: -10, gap_extend: -1, substitution: None };
+/// let (s, b) = align_global_affine("ACGTTTTACG", "ACGACG", &opt);
+///
+/// let alignment = traceback_global_affine(&s, &b, "ACGTTTTACG", "ACGACG");
+///
+/// assert_eq!(alignment.aligned_v, "ACG----ACG");
+/// assert_eq!(alignment.score, 17);
+/// ```
+pub fn traceback_global_affine<U, V>(s: &Matrix<i32>, tb: &AffineTraceback, u: U, v: V) -> Alignment
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let mut operations = Vec::new();
+
+    let mut i = tb.b.row() - 1;
+    let mut j = tb.b.col() - 1;
+
+    // It stores the state being backtracked, that is, the diagonal if it
+    // is the best state, up if it is inside an upper gap and left if it
+    // is inside a left gap.
+    let mut state = Traceback::DIAGONAL;
+
+    while i > 0 || j > 0 {
+        if state == Traceback::DIAGONAL {
+            state = tb.b[(i, j)].preferred();
+        }
+
+        if state == Traceback::DIAGONAL {
+            operations.push(if u[i - 1] == v[j - 1] {
+                Operation::Match
+            } else {
+                Operation::Mismatch
+            });
+            i -= 1;
+            j -= 1;
+        } else if state == Traceback::UP {
+            operations.push(Operation::Deletion);
+            state = if tb.up_extend[(i, j)] {
+                Traceback::UP
+            } else {
+                Traceback::DIAGONAL
+            };
+            i -= 1;
+        } else {
+            operations.push(Operation::Insertion);
+            state = if tb.left_extend[(i, j)] {
+                Traceback::LEFT
+            } else {
+                Traceback::DIAGONAL
+            };
+            j -= 1;
+        }
+    }
+
+    operations.reverse();
+
+    Alignment::new(u, v, 0, 0, operations, s[(s.row() - 1, s.col() - 1)])
+}
+
+/// Prints the affine gap global alignment between the sequences `u` and `v`.
+///
+/// Receiving the `s` that represents the constructed `matrix` to calculate the
+/// alignment score, the `tb` that represents the previously constructed
+/// [`AffineTraceback`] and the two sequences being aligned, the global
+/// alignment is printed to the standard output.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{align_global_affine, print_align_global_affine, AffineOptions};
+/// let opt = AffineOptions { match_: 5, mismatch: -3, gap_open: -10, gap_extend: -1, substitution: None };
+/// let (s, b) = align_global_affine("ACGTTTTACG", "ACGACG", &opt);
+///
+/// print_align_global_affine(&s, &b, "ACGTTTTACG", "ACGACG");
+/// ```
+///
+/// The code snippet above once is run, it will print to the standard output
+/// the following.
+///
+/// ```text
+/// ACGTTTTACG
+/// ACG----ACG
+/// ```
+pub fn print_align_global_affine<U, V>(s: &Matrix<i32>, tb: &AffineTraceback, u: U, v: V)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    println!("{}", traceback_global_affine(s, tb, u, v));
+}
+
+/// Returns the score of the affine gap global alignment between the sequences `u` and `v`.
+///
+/// Further, a tuple is returned containing in the first component the optimal score
+/// and in the second and third components the end coordinates of the alignment in
+/// `u` and `v`, which for a global alignment are always the sequence lengths.
+///
+/// As in [`align_global_affine`], the Gotoh algorithm is applied, though only two rows
+/// of the score matrix and the row of upper gap scores are kept. Therefore, it runs in
+/// linear memory.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{score_global_affine, AffineOptions};
+/// let opt = AffineOptions { match_: 5, mismatch: -3, gap_open: -10, gap_extend: -1, substitution: None };
+///
+/// assert_eq!(score_global_affine("ACGTTTTACG", "ACGACG", &opt), (17, 10, 6));
+/// ```
+pub fn score_global_affine<U, V>(u: U, v: V, opt: &AffineOptions) -> (i32, usize, usize)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let gap_open = opt.gap_open as i32;
+    let gap_extend = opt.gap_extend as i32;
+
+    // It initializes the first row with a single gap.
+    let mut previous = (0..=v.len())
+        .map(|j| match j {
+            0 => 0,
+            _ => gap_open + ((j - 1) as i32) * gap_extend,
+        })
+        .collect::<Vec<i32>>();
+    let mut current = vec![0i32; v.len() + 1];
+    let mut upper_gap = vec![NEG_INF; v.len() + 1];
+
+    for (i, &a) in u.iter().enumerate() {
+        current[0] = gap_open + (i as i32) * gap_extend;
+
+        let mut left_gap = NEG_INF;
+
+        for (j, &b) in v.iter().enumerate() {
+            let diagonal = previous[j] + opt.score(a, b);
+
+            upper_gap[j + 1] = (previous[j + 1] + gap_open).max(upper_gap[j + 1] + gap_extend);
+            left_gap = (current[j] + gap_open).max(left_gap + gap_extend);
+
+            current[j + 1] = diagonal.max(upper_gap[j + 1]).max(left_gap);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    (previous[v.len()], u.len(), v.len())
+}
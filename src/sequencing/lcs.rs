@@ -22,29 +22,67 @@
  * SOFTWARE.
  */
 use crate::math::matrix::Matrix;
+use crate::sequencing::alignment::{Alignment, Operation};
+use crate::sequencing::traceback::Traceback;
 
 /// It calculates the `longest common subsequence` between the two given
 /// sequences `u` and `v`.
 ///
 /// Further, it is returned ot the user a tuple containing in the first
 /// component the matrix that contains the calculated values and, in the
-/// second component the matrix that contains the `arrows` that may used
-/// to be backtracked and mount the longest common subsequence.
-pub fn lcs(u: &str, v: &str) -> (Matrix<u32>, Matrix<char>) {
+/// second component the [`Traceback`] matrix that may be backtracked to
+/// mount the longest common subsequence. If the upper and the left cells
+/// are tied, then both of them are stored as predecessors.
+pub fn lcs<U, V>(u: U, v: V) -> (Matrix<u32>, Matrix<Traceback>)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
     let mut s = Matrix::<u32>::new(u.len() + 1, v.len() + 1, 0u32);
-    let mut b = Matrix::<char>::new(u.len() + 1, v.len() + 1, ' ');
+    let mut b = Matrix::<Traceback>::new(u.len() + 1, v.len() + 1, Traceback::NONE);
 
+    // It initializes the first column and the first row with the `arrows`
+    // that skip the remaining characters of each sequence.
     for i in 1..s.row() {
-        for j in 1..s.col() {
-            if u.chars().nth(i - 1).unwrap() == v.chars().nth(j - 1).unwrap() {
-                s[i][j] = s[i - 1][j - 1] + 1;
-                b[i][j] = 'd';
-            } else if s[i - 1][j] >= s[i][j - 1] {
-                s[i][j] = s[i - 1][j];
-                b[i][j] = 'u';
-            } else {
-                s[i][j] = s[i][j - 1];
-                b[i][j] = 'l';
+        b[(i, 0)] = Traceback::UP;
+    }
+
+    for j in 1..s.col() {
+        b[(0, j)] = Traceback::LEFT;
+    }
+
+    // It calculates each cell from its neighbours, without checking their
+    // bounds in the inner loop.
+    for (i, &a) in (1..s.row()).zip(u.iter()) {
+        for (j, &c) in (1..s.col()).zip(v.iter()) {
+            // SAFETY: `1 <= i < s.row()` and `1 <= j < s.col()`, hence the cell
+            // `(i, j)` and its upper, left and upper left neighbours are inside
+            // `s`, and `b` has the same size as `s`.
+            unsafe {
+                if a == c {
+                    *s.get_unchecked_mut(i, j) = *s.get_unchecked(i - 1, j - 1) + 1;
+                    *b.get_unchecked_mut(i, j) = Traceback::DIAGONAL;
+                    continue;
+                }
+
+                let upper = *s.get_unchecked(i - 1, j);
+                let left = *s.get_unchecked(i, j - 1);
+                let best = upper.max(left);
+
+                let mut arrow = Traceback::NONE;
+
+                if upper == best {
+                    arrow |= Traceback::UP;
+                }
+
+                if left == best {
+                    arrow |= Traceback::LEFT;
+                }
+
+                *s.get_unchecked_mut(i, j) = best;
+                *b.get_unchecked_mut(i, j) = arrow;
             }
         }
     }
@@ -56,26 +94,70 @@ pub fn lcs(u: &str, v: &str) -> (Matrix<u32>, Matrix<char>) {
     (s, b)
 }
 
-/// It prints the `longest common subsequence` receiving the matrix
-/// containing the `arrows` and one of the strings.
-pub fn print_lcs(b: Matrix<char>, u: &str) {
-    print_lcs_rec(&b, u, b.row() - 1, b.col() - 1);
-    println!("");
+/// It returns the alignment between the sequences `u` and `v` that mounts
+/// the `longest common subsequence`, receiving the matrix containing the
+/// calculated values, the [`Traceback`] matrix and the strings.
+///
+/// The longest common subsequence is formed by the residues of the
+/// [`Operation::Match`] columns, and the alignment score is its length.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::lcs::{lcs, traceback_lcs};
+/// let (s, b) = lcs("ABCBDAB", "BDCABA");
+///
+/// let alignment = traceback_lcs(&s, &b, "ABCBDAB", "BDCABA");
+///
+/// assert_eq!(alignment.score, 4);
+/// ```
+pub fn traceback_lcs<U, V>(s: &Matrix<u32>, b: &Matrix<Traceback>, u: U, v: V) -> Alignment
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let i = b.row() - 1;
+    let j = b.col() - 1;
+
+    Alignment::from_traceback(b, u, v, i, j, s[(i, j)] as i32)
 }
 
-/// It prints the `longest common subsequence` receiveing the matrix
-/// containing the `arrows`, the string and the start cell (i, j).
-fn print_lcs_rec(b: &Matrix<char>, u: &str, i: usize, j: usize) {
-    if i == 0 || j == 0 {
-        return;
-    }
+/// It prints the `longest common subsequence` receiving the matrix
+/// containing the calculated values, the [`Traceback`] matrix and the
+/// strings.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::lcs::{lcs, print_lcs};
+/// let (s, b) = lcs("ABCBDAB", "BDCABA");
+///
+/// print_lcs(&s, &b, "ABCBDAB", "BDCABA");
+/// ```
+///
+/// The code snippet above once is run, it will print to the standard output
+/// the following.
+///
+/// ```text
+/// BCBA
+/// ```
+pub fn print_lcs<U, V>(s: &Matrix<u32>, b: &Matrix<Traceback>, u: U, v: V)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let alignment = traceback_lcs(s, b, u, v);
 
-    if b[i][j] == 'd' {
-        print_lcs_rec(b, u, i - 1, j - 1);
-        print!("{}", u.chars().nth(i - 1).unwrap());
-    } else if b[i][j] == 'u' {
-        print_lcs_rec(b, u, i - 1, j);
-    } else {
-        print_lcs_rec(b, u, i, j - 1);
-    }
+    println!(
+        "{}",
+        alignment
+            .aligned_u
+            .chars()
+            .zip(alignment.operations.iter())
+            .filter(|(_, &op)| op == Operation::Match)
+            .map(|(c, _)| c)
+            .collect::<String>()
+    );
 }
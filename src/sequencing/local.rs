@@ -1,4 +1,12 @@
 use crate::math::matrix::Matrix;
+use crate::sequencing::alignment::{Alignment, Operation};
+use crate::sequencing::cooptimal::CoOptimalAlignments;
+use crate::sequencing::scoring::{max_residue_score, residue_score, NEG_INF};
+use crate::sequencing::sequence::{align_stranded, Strand};
+use crate::sequencing::substitution::SubstitutionMatrix;
+use crate::sequencing::traceback::Traceback;
+
+pub use crate::sequencing::scoring::AffineTraceback;
 
 /// Local Alignment Options.
 ///
@@ -8,26 +16,61 @@ use crate::math::matrix::Matrix;
 /// # Examples
 ///
 /// ```
+/// # use bioinformatics::sequencing::local::Options;
 /// let opt = Options {
 ///     match_: 5,
 ///     mismatch: -3,
 ///     gap: -4,
+///     substitution: None,
 /// };
 /// ```
 ///
 /// The code snippet above defines a function with match point equals to `5`,
 /// mismatch point equals to `-3` and gap point equals to `-4`.
+///
+/// # Substitution Matrices
+///
+/// If a [`SubstitutionMatrix`] is specified, then it is used to score the
+/// aligned residues instead of the `match` and `mismatch` points.
+///
+/// ```
+/// # use bioinformatics::sequencing::local::Options;
+/// # use bioinformatics::sequencing::substitution::SubstitutionMatrix;
+/// let opt = Options {
+///     match_: 0,
+///     mismatch: 0,
+///     gap: -8,
+///     substitution: Some(SubstitutionMatrix::blosum62()),
+/// };
+///
+/// assert_eq!(opt.score(b'I', b'L'), 2);
+/// ```
 pub struct Options {
     pub match_: i8,
     pub mismatch: i8,
     pub gap: i8,
+    pub substitution: Option<SubstitutionMatrix>,
+}
+
+impl Options {
+    /// Returns the score of aligning the residue `a` against the residue `b`.
+    pub fn score(&self, a: u8, b: u8) -> i32 {
+        residue_score(self.substitution.as_ref(), self.match_, self.mismatch, a, b)
+    }
+
+    /// Returns the highest score of aligning two residues.
+    pub fn max_score(&self) -> i32 {
+        max_residue_score(self.substitution.as_ref(), self.match_, self.mismatch)
+    }
 }
 
 /// Align two sequences locally.
 ///
 /// Further, a tuple is returned containing in the first component the constructed
-/// `matrix` used to calculate the alignment score and the second component that is
-/// used by the alignment printers that contains the `arrow`s.
+/// `matrix` used to calculate the alignment score and the second component the
+/// [`Traceback`] matrix used to backtrack the alignment, in which every optimal
+/// predecessor of each cell is stored. The cells in which the score is reset to
+/// zero have no predecessor.
 ///
 /// It applies the alignment between the two specified sequences using the
 /// Smith-Waterman algorithm.
@@ -39,61 +82,55 @@ pub struct Options {
 /// # Examples
 ///
 /// ```
-/// let (s, b) = align_global("GGAGACCATTATG", "CCAATATG", &Options { match_: 5, mismatch: -3, gap: -4 });
+/// # use bioinformatics::sequencing::local::{align_local, print_align_local, Options};
+/// let (s, b) = align_local("GGAGACCATTATG", "CCAATATG", &Options { match_: 5, mismatch: -3, gap: -4, substitution: None });
 ///
-/// print_align_local(s, b, "GGAGACCATTATG", "CCAATATG");
+/// print_align_local(&s, &b, "GGAGACCATTATG", "CCAATATG");
 /// ```
 ///
 /// The code snippet above performs the local alignment between the two specified sequences
 /// being them `GGAGACCATTATG` and `CCAATATG` and prints the local alignment.
 ///
 /// ```text
-/// CATTATG
-/// CAATATG
+/// CCATTATG
+/// CCAATATG
 /// ```
 ///
 /// Further, the maximum score from the local alignment can be obtained using `s.max()`.
 ///
 /// ```
-/// let (s, b) = align_global("GGAGACCATTATG", "CCAATATG", &Options { match_: 5, mismatch: -3, gap: -4 });
+/// # use bioinformatics::sequencing::local::{align_local, Options};
+/// let (s, b) = align_local("GGAGACCATTATG", "CCAATATG", &Options { match_: 5, mismatch: -3, gap: -4, substitution: None });
 ///
-/// assert_eq!(s.max(), 27);
+/// assert_eq!(s.max(), &32);
 /// ```
-pub fn align_local(u: &str, v: &str, opt: &Options) -> (Matrix<i32>, Matrix<char>) {
+pub fn align_local<U, V>(u: U, v: V, opt: &Options) -> (Matrix<i32>, Matrix<Traceback>)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
     let mut s = Matrix::<i32>::new(u.len() + 1, v.len() + 1, 0i32);
-    let mut b = Matrix::<char>::new(u.len() + 1, v.len() + 1, ' ');
+    let mut b = Matrix::<Traceback>::new(u.len() + 1, v.len() + 1, Traceback::NONE);
 
-    let u_chars = u.chars().collect::<Vec<char>>();
-    let v_chars = v.chars().collect::<Vec<char>>();
+    let gap = opt.gap as i32;
 
     // It calculates the matrix values using the previously specified
-    // score function (match, mismatch and gap).
-    for i in 1..s.row() {
-        for j in 1..s.col() {
-            let diagonal = s[i - 1][j - 1]
-                + (if u_chars[i - 1] == v_chars[j - 1] { 
-                    opt.match_ as i32
-                } else {
-                    opt.mismatch as i32
-                });
-            let upper = s[i - 1][j] + (opt.gap as i32);
-            let left = s[i][j - 1] + (opt.gap as i32);
-
-            if diagonal < 0 && upper < 0 && left < 0 {
-                s[i][j] = 0;
-                b[i][j] = 's';
-                continue;
-            }
+    // score function (match, mismatch or substitution matrix, and gap),
+    // without checking the bounds of the cells in the inner loop.
+    for (i, &a) in (1..s.row()).zip(u.iter()) {
+        for (j, &c) in (1..s.col()).zip(v.iter()) {
+            // SAFETY: `1 <= i < s.row()` and `1 <= j < s.col()`, hence the cell
+            // `(i, j)` and its upper, left and upper left neighbours are inside
+            // `s`, and `b` has the same size as `s`.
+            unsafe {
+                let diagonal = *s.get_unchecked(i - 1, j - 1) + opt.score(a, c);
+                let upper = *s.get_unchecked(i - 1, j) + gap;
+                let left = *s.get_unchecked(i, j - 1) + gap;
 
-            if diagonal >= upper && diagonal >= left {
-                s[i][j] = diagonal;
-                b[i][j] = 'd';
-            } else if diagonal < upper && upper >= left {
-                s[i][j] = upper;
-                b[i][j] = 'u';
-            } else {
-                s[i][j] = left;
-                b[i][j] = 'l';
+                (*s.get_unchecked_mut(i, j), *b.get_unchecked_mut(i, j)) =
+                    local_cell(diagonal, upper, left);
             }
         }
     }
@@ -101,10 +138,52 @@ pub fn align_local(u: &str, v: &str, opt: &Options) -> (Matrix<i32>, Matrix<char
     (s, b)
 }
 
+/// Returns the score and the [`Traceback`] of a cell using the Smith-Waterman
+/// recurrence, given its `diagonal`, `upper` and `left` candidate scores.
+fn local_cell(diagonal: i32, upper: i32, left: i32) -> (i32, Traceback) {
+    let best = diagonal.max(upper).max(left);
+
+    if best < 0 {
+        (0, Traceback::NONE)
+    } else {
+        (best, Traceback::from_scores(best, diagonal, upper, left))
+    }
+}
+
+/// Returns the local alignment between the sequences `u` and `v`.
+///
+/// Receiving the `s` that represents the constructed `matrix` to calculate
+/// the maximum score, the `b` that represents the constructed [`Traceback`] matrix
+/// and the two sequences being aligned, the preferred predecessors are backtracked
+/// from the cell with the maximum score to construct the [`Alignment`].
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::{align_local, traceback_local, Options};
+/// let (s, b) = align_local("GGAGACCATTATG", "CCAATATG", &Options { match_: 5, mismatch: -3, gap: -4, substitution: None });
+///
+/// let alignment = traceback_local(&s, &b, "GGAGACCATTATG", "CCAATATG");
+///
+/// assert_eq!(alignment.aligned_u, "CCATTATG");
+/// assert_eq!(alignment.score, 32);
+/// ```
+pub fn traceback_local<U, V>(s: &Matrix<i32>, b: &Matrix<Traceback>, u: U, v: V) -> Alignment
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let (mv, i, j) = s.max_with_pos();
+
+    Alignment::from_traceback(b, u, v, i, j, *mv)
+}
+
 /// Prints the local alignment between the sequences `u` and `v`.
 ///
 /// Receiving the `s` that represents the constructed `matrix` to calculate
-/// the maximum score, the `b` matrix containing the constructed `arrow` matrix
+/// the maximum score, the `b` that represents the constructed [`Traceback`] matrix
 /// and the two sequences being aligned.
 ///
 /// The local alignment is printed to the standard output.
@@ -112,67 +191,559 @@ pub fn align_local(u: &str, v: &str, opt: &Options) -> (Matrix<i32>, Matrix<char
 /// # Examples
 ///
 /// ```
-/// let (s, b) = align_global("GGAGACCATTATG", "CCAATATG", &Options { match_: 5, mismatch: -3, gap: -4 });
+/// # use bioinformatics::sequencing::local::{align_local, print_align_local, Options};
+/// let (s, b) = align_local("GGAGACCATTATG", "CCAATATG", &Options { match_: 5, mismatch: -3, gap: -4, substitution: None });
 ///
-/// print_align_local(s, b, "GGAGACCATTATG", "CCAATATG");
+/// print_align_local(&s, &b, "GGAGACCATTATG", "CCAATATG");
 /// ```
 ///
 /// The code snippet above performs the local alignment between the two specified sequences
 /// being them `GGAGACCATTATG` and `CCAATATG` and prints the local alignment.
 ///
 /// ```text
-/// CATTATG
-/// CAATATG
+/// CCATTATG
+/// CCAATATG
 /// ```
-pub fn print_align_local(s: &Matrix<i32>, b: &Matrix<char>, u: &str, v: &str) {
-    let mut aligned_u = String::from("");
-    let mut aligned_v = String::from("");
+pub fn print_align_local<U, V>(s: &Matrix<i32>, b: &Matrix<Traceback>, u: U, v: V)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    println!("{}", traceback_local(s, b, u, v));
+}
 
-    let (_mv, i, j) = s.max_with_pos();
+/// Returns the score of the local alignment between the sequences `u` and `v`.
+///
+/// Further, a tuple is returned containing in the first component the maximum score
+/// and in the second and third components the end coordinates of the alignment in
+/// `u` and `v`. The end cell is the first cell with the maximum score in row-major
+/// order, as in [`traceback_local`].
+///
+/// Unlike [`align_local`], neither the score matrix nor the [`Traceback`] matrix is
+/// built, since only two rows are kept at a time. Therefore, it runs in linear memory.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::{score_local, Options};
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+///
+/// assert_eq!(score_local("GGAGACCATTATG", "CCAATATG", &opt), (32, 13, 8));
+/// ```
+pub fn score_local<U, V>(u: U, v: V, opt: &Options) -> (i32, usize, usize)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let gap = opt.gap as i32;
+
+    let mut previous = vec![0i32; v.len() + 1];
+    let mut current = vec![0i32; v.len() + 1];
+    let mut best = (0, 0, 0);
+
+    for (i, &a) in u.iter().enumerate() {
+        for (j, &b) in v.iter().enumerate() {
+            let diagonal = previous[j] + opt.score(a, b);
+            let upper = previous[j + 1] + gap;
+            let left = current[j] + gap;
 
-    print_align_local_rec(b, u, v, &mut aligned_u, &mut aligned_v, i, j);
+            current[j + 1] = diagonal.max(upper).max(left).max(0);
 
-    println!("{}", aligned_u.chars().rev().collect::<String>());
-    println!("{}", aligned_v.chars().rev().collect::<String>());
+            if current[j + 1] > best.0 {
+                best = (current[j + 1], i + 1, j + 1);
+            }
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    best
 }
 
-/// Prints the local alignment between two sequences `u` and `v`.
+/// Align the sequence `u` in both orientations locally against `v`.
 ///
-/// This function performs the printing of the local alignment recursively.
-/// Moreover, this function is set `private` because it requests much arguments
-/// to eprform the printing and some of its arguments are not needed to be initialized
-/// by the library users.
+/// Since the strand of a read is usually unknown, the read `u` is aligned as it is and
+/// as its [reverse complement](crate::sequencing::sequence::reverse_complement), and
+/// the alignment with the highest score is returned together with its [`Strand`]. If
+/// both scores are equal, then the forward strand is chosen.
 ///
-/// Therefore, the function [`print_align_local`] is used to provide an easier
-/// way to print the local alignment between two sequences.
-fn print_align_local_rec(
-    b: &Matrix<char>,
-    u: &str,
-    v: &str,
-    aligned_u: &mut String,
-    aligned_v: &mut String,
-    i: usize,
-    j: usize,
-) {
-    if i == 0 || j == 0 {
-        return;
-    }
+/// The coordinates of `u` in a [`Strand::Reverse`] alignment refer to the reverse
+/// complement of `u`, that is also the sequence shown in `aligned_u`, while the
+/// coordinates of `v` are not affected.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::{align_local_stranded, Options};
+/// # use bioinformatics::sequencing::sequence::Strand;
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let (alignment, strand) = align_local_stranded("TTTCATAATGGTTTT", "GGGGACCATTATGGGGG", &opt);
+///
+/// assert_eq!(strand, Strand::Reverse);
+/// assert_eq!(alignment.aligned_v, "ACCATTATG");
+/// assert_eq!(alignment.score, 45);
+/// ```
+pub fn align_local_stranded<U, V>(u: U, v: V, opt: &Options) -> (Alignment, Strand)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let v = v.as_ref();
+
+    align_stranded(u.as_ref(), |u| {
+        let (s, b) = align_local(u, v, opt);
+        traceback_local(&s, &b, u, v)
+    })
+}
+
+/// Returns the `k` best non-overlapping local alignments between the sequences
+/// `u` and `v`, in decreasing score order.
+///
+/// It applies the Waterman-Eggert algorithm, that is, after the best local alignment
+/// is found, the cells of its path are forbidden (their score is set to zero) and
+/// the Smith-Waterman matrix is recalculated to find the next best one. Hence, no
+/// two returned alignments share an aligned pair of residues nor a gap cell.
+///
+/// Since only the cells below and to the right of the forbidden path may change,
+/// the matrix is only recalculated from the path onwards, and the recalculation
+/// stops at the first row below the path whose values do not change.
+///
+/// Fewer than `k` alignments are returned if no other alignment with a positive
+/// score exists.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::{align_local_suboptimal, Options};
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let alignments = align_local_suboptimal("CCATTATGAAAAACCATTAT", "CCATTATG", &opt, 3);
+///
+/// assert_eq!(alignments[0].score, 40);
+/// assert_eq!((alignments[0].u_start, alignments[0].u_end), (0, 8));
+/// assert_eq!(alignments[1].score, 35);
+/// assert_eq!((alignments[1].u_start, alignments[1].u_end), (13, 20));
+/// ```
+pub fn align_local_suboptimal<U, V>(u: U, v: V, opt: &Options, k: usize) -> Vec<Alignment>
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let (mut s, mut b) = align_local(u, v, opt);
+
+    let mut forbidden = Matrix::<bool>::new(s.row(), s.col(), false);
+    let mut alignments = Vec::new();
+
+    while alignments.len() < k {
+        let (&score, i, j) = s.max_with_pos();
+
+        if score <= 0 {
+            break;
+        }
+
+        let alignment = Alignment::from_traceback(&b, u, v, i, j, score);
+
+        // It forbids every cell in the path of the alignment, except its
+        // start cell, that is not part of the alignment.
+        let (mut i, mut j) = (alignment.u_start, alignment.v_start);
+
+        for op in alignment.operations.iter() {
+            match op {
+                Operation::Match | Operation::Mismatch => {
+                    i += 1;
+                    j += 1;
+                }
+                Operation::Deletion => i += 1,
+                Operation::Insertion => j += 1,
+            }
+
+            forbidden[(i, j)] = true;
+        }
+
+        // It recalculates the cells that may depend on the forbidden ones.
+        for i in (alignment.u_start + 1)..s.row() {
+            let mut changed = false;
 
-    if b[i][j] == 's' {
-        return;
+            for j in (alignment.v_start + 1)..s.col() {
+                let (score, arrow) = if forbidden[(i, j)] {
+                    (0, Traceback::NONE)
+                } else {
+                    local_cell(
+                        s[(i - 1, j - 1)] + opt.score(u[i - 1], v[j - 1]),
+                        s[(i - 1, j)] + (opt.gap as i32),
+                        s[(i, j - 1)] + (opt.gap as i32),
+                    )
+                };
+
+                if s[(i, j)] != score || b[(i, j)] != arrow {
+                    s[(i, j)] = score;
+                    b[(i, j)] = arrow;
+                    changed = true;
+                }
+            }
+
+            if !changed && i > alignment.u_end {
+                break;
+            }
+        }
+
+        alignments.push(alignment);
     }
 
-    if b[i][j] == 'd' {
-        *aligned_u += &u.chars().nth(i - 1).unwrap().to_string();
-        *aligned_v += &v.chars().nth(j - 1).unwrap().to_string();
-        print_align_local_rec(b, u, v, aligned_u, aligned_v, i - 1, j - 1);
-    } else if b[i][j] == 'u' {
-        *aligned_u += &u.chars().nth(i - 1).unwrap().to_string();
-        *aligned_v += "-";
-        print_align_local_rec(b, u, v, aligned_u, aligned_v, i - 1, j);
+    alignments
+}
+
+/// Returns an iterator over every co-optimal local alignment between the
+/// sequences `u` and `v`.
+///
+/// Receiving the `s` and `b` matrices constructed by [`align_local`] and the two
+/// sequences being aligned, every path of optimal predecessors from each cell with
+/// the maximum score is backtracked, in row-major order of these cells. Hence, the
+/// first alignment is the same one returned by [`traceback_local`]. If a `limit`
+/// is specified, then at most `limit` alignments are yielded.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::{align_local, traceback_local_cooptimal, Options};
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let (s, b) = align_local("ACGTTTACG", "ACG", &opt);
+///
+/// let alignments = traceback_local_cooptimal(&s, &b, "ACGTTTACG", "ACG", None).collect::<Vec<_>>();
+///
+/// assert_eq!(alignments.len(), 2);
+/// assert_eq!((alignments[0].u_start, alignments[1].u_start), (0, 6));
+///
+/// assert_eq!(traceback_local_cooptimal(&s, &b, "ACGTTTACG", "ACG", Some(1)).count(), 1);
+/// ```
+pub fn traceback_local_cooptimal<'a, U, V>(
+    s: &Matrix<i32>,
+    b: &'a Matrix<Traceback>,
+    u: &'a U,
+    v: &'a V,
+    limit: Option<usize>,
+) -> CoOptimalAlignments<'a>
+where
+    U: AsRef<[u8]> + ?Sized,
+    V: AsRef<[u8]> + ?Sized,
+{
+    let max = *s.max();
+
+    let mut ends = Vec::new();
+
+    // An empty alignment is the only optimal one if no cell has a positive score.
+    if max > 0 {
+        for i in 0..s.row() {
+            for j in 0..s.col() {
+                if s[(i, j)] == max {
+                    ends.push((i, j));
+                }
+            }
+        }
     } else {
-        *aligned_u += "-";
-        *aligned_v += &v.chars().nth(j - 1).unwrap().to_string();
-        print_align_local_rec(b, u, v, aligned_u, aligned_v, i, j - 1);
+        ends.push((0, 0));
+    }
+
+    CoOptimalAlignments::new(b, u.as_ref(), v.as_ref(), ends, max, limit)
+}
+
+/// Local Alignment Affine Gap Options.
+///
+/// This structure provides a manner to specify the `score` function that
+/// comprises the `match` point, `mismatch` point, `gap open` point and the
+/// `gap extend` point.
+///
+/// The first position of a gap is scored with `gap_open`, while each of its
+/// following positions is scored with `gap_extend`. Therefore, a gap with
+/// length `k` is scored as `gap_open + (k - 1) * gap_extend`.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::AffineOptions;
+/// let opt = AffineOptions {
+///     match_: 5,
+///     mismatch: -3,
+///     gap_open: -10,
+///     gap_extend: -1,
+///     substitution: None,
+/// };
+/// ```
+///
+/// The code snippet above defines a function with match point equals to `5`,
+/// mismatch point equals to `-3`, gap open point equals to `-10` and gap extend
+/// point equals to `-1`.
+///
+/// As in [`Options`], if a [`SubstitutionMatrix`] is specified, then it is used
+/// to score the aligned residues instead of the `match` and `mismatch` points.
+pub struct AffineOptions {
+    pub match_: i8,
+    pub mismatch: i8,
+    pub gap_open: i8,
+    pub gap_extend: i8,
+    pub substitution: Option<SubstitutionMatrix>,
+}
+
+impl AffineOptions {
+    /// Returns the score of aligning the residue `a` against the residue `b`.
+    pub fn score(&self, a: u8, b: u8) -> i32 {
+        residue_score(self.substitution.as_ref(), self.match_, self.mismatch, a, b)
+    }
+}
+
+/// Align two sequences locally using affine gap points.
+///
+/// Further, a tuple is returned containing in the first component the constructed
+/// `matrix` used to calculate the alignment score and the second component the
+/// [`AffineTraceback`] used to backtrack the alignment.
+///
+/// It applies the local alignment between the two specified sequences using the
+/// Smith-Waterman algorithm extended with the three matrices from the Gotoh algorithm
+/// to distinguish opening a gap from extending it.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::{align_local_affine, print_align_local_affine, AffineOptions};
+/// let opt = AffineOptions { match_: 5, mismatch: -3, gap_open: -10, gap_extend: -1, substitution: None };
+/// let (s, b) = align_local_affine("TTGCATCGAAAAGCTATT", "CCGCATCGGCTACC", &opt);
+///
+/// assert_eq!(s.max(), &37);
+///
+/// print_align_local_affine(&s, &b, "TTGCATCGAAAAGCTATT", "CCGCATCGGCTACC");
+/// ```
+///
+/// The code snippet above performs the local alignment between the two specified sequences
+/// being them `TTGCATCGAAAAGCTATT` and `CCGCATCGGCTACC` and prints the local alignment.
+///
+/// ```text
+/// GCATCGAAAAGCTA
+/// GCATCG----GCTA
+/// ```
+pub fn align_local_affine<U, V>(u: U, v: V, opt: &AffineOptions) -> (Matrix<i32>, AffineTraceback)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let mut s = Matrix::<i32>::new(u.len() + 1, v.len() + 1, 0i32);
+    let mut tb = AffineTraceback {
+        b: Matrix::<Traceback>::new(u.len() + 1, v.len() + 1, Traceback::NONE),
+        up_extend: Matrix::<bool>::new(u.len() + 1, v.len() + 1, false),
+        left_extend: Matrix::<bool>::new(u.len() + 1, v.len() + 1, false),
+    };
+
+    let gap_open = opt.gap_open as i32;
+    let gap_extend = opt.gap_extend as i32;
+
+    // It stores the best score of the alignments ending with an upper gap
+    // in the previous row. Only the previous row is needed, since the upper
+    // gaps are extended from the cell right above.
+    let mut upper_gap = vec![NEG_INF; s.col()];
+
+    // It calculates the matrices values using the previously specified
+    // score function (match, mismatch or substitution matrix, gap open
+    // and gap extend).
+    for i in 1..s.row() {
+        // It stores the best score of the alignments ending with a left gap
+        // in the current row.
+        let mut left_gap = NEG_INF;
+
+        for j in 1..s.col() {
+            let diagonal = s[(i - 1, j - 1)] + opt.score(u[i - 1], v[j - 1]);
+
+            let upper_open = s[(i - 1, j)] + gap_open;
+            let upper_extend = upper_gap[j] + gap_extend;
+            tb.up_extend[(i, j)] = upper_extend > upper_open;
+            upper_gap[j] = upper_open.max(upper_extend);
+
+            let left_open = s[(i, j - 1)] + gap_open;
+            let left_extend = left_gap + gap_extend;
+            tb.left_extend[(i, j)] = left_extend > left_open;
+            left_gap = left_open.max(left_extend);
+
+            let upper = upper_gap[j];
+            let left = left_gap;
+
+            let best = diagonal.max(upper).max(left);
+
+            if best < 0 {
+                s[(i, j)] = 0;
+                tb.b[(i, j)] = Traceback::NONE;
+                continue;
+            }
+
+            s[(i, j)] = best;
+            tb.b[(i, j)] = Traceback::from_scores(best, diagonal, upper, left);
+        }
     }
+
+    (s, tb)
+}
+
+/// Returns the affine gap local alignment between the sequences `u` and `v`.
+///
+/// Receiving the `s` that represents the constructed `matrix` to calculate
+/// the maximum score, the `tb` containing the constructed [`AffineTraceback`]
+/// and the two sequences being aligned, the gap states are backtracked from
+/// the cell with the maximum score to construct the [`Alignment`].
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::{align_local_affine, traceback_local_affine, AffineOptions};
+/// let opt = AffineOptions { match_: 5, mismatch: -3, gap_open: -10, gap_extend: -1, substitution: None };
+/// let (s, b) = align_local_affine("TTGCATCGAAAAGCTATT", "CCGCATCGGCTACC", &opt);
+///
+/// let alignment = traceback_local_affine(&s, &b, "TTGCATCGAAAAGCTATT", "CCGCATCGGCTACC");
+///
+/// assert_eq!(alignment.aligned_v, "GCATCG----GCTA");
+/// assert_eq!((alignment.u_start, alignment.u_end), (2, 16));
+/// assert_eq!(alignment.score, 37);
+/// ```
+pub fn traceback_local_affine<U, V>(s: &Matrix<i32>, tb: &AffineTraceback, u: U, v: V) -> Alignment
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let mut operations = Vec::new();
+
+    let (mv, mut i, mut j) = s.max_with_pos();
+
+    // It stores the state being backtracked, that is, the diagonal if it
+    // is the best state, up if it is inside an upper gap and left if it
+    // is inside a left gap.
+    let mut state = Traceback::DIAGONAL;
+
+    while i > 0 && j > 0 {
+        if state == Traceback::DIAGONAL {
+            state = tb.b[(i, j)].preferred();
+        }
+
+        if state == Traceback::DIAGONAL {
+            operations.push(if u[i - 1] == v[j - 1] {
+                Operation::Match
+            } else {
+                Operation::Mismatch
+            });
+            i -= 1;
+            j -= 1;
+        } else if state == Traceback::UP {
+            operations.push(Operation::Deletion);
+            state = if tb.up_extend[(i, j)] {
+                Traceback::UP
+            } else {
+                Traceback::DIAGONAL
+            };
+            i -= 1;
+        } else if state == Traceback::LEFT {
+            operations.push(Operation::Insertion);
+            state = if tb.left_extend[(i, j)] {
+                Traceback::LEFT
+            } else {
+                Traceback::DIAGONAL
+            };
+            j -= 1;
+        } else {
+            break;
+        }
+    }
+
+    operations.reverse();
+
+    Alignment::new(u, v, i, j, operations, *mv)
+}
+
+/// Prints the affine gap local alignment between the sequences `u` and `v`.
+///
+/// Receiving the `s` that represents the constructed `matrix` to calculate
+/// the maximum score, the `tb` containing the constructed [`AffineTraceback`]
+/// and the two sequences being aligned.
+///
+/// The local alignment is printed to the standard output.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::{align_local_affine, print_align_local_affine, AffineOptions};
+/// let opt = AffineOptions { match_: 5, mismatch: -3, gap_open: -10, gap_extend: -1, substitution: None };
+/// let (s, b) = align_local_affine("TTGCATCGAAAAGCTATT", "CCGCATCGGCTACC", &opt);
+///
+/// print_align_local_affine(&s, &b, "TTGCATCGAAAAGCTATT", "CCGCATCGGCTACC");
+/// ```
+///
+/// The code snippet above once is run, it will print to the standard output
+/// the following.
+///
+/// ```text
+/// GCATCGAAAAGCTA
+/// GCATCG----GCTA
+/// ```
+pub fn print_align_local_affine<U, V>(s: &Matrix<i32>, tb: &AffineTraceback, u: U, v: V)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    println!("{}", traceback_local_affine(s, tb, u, v));
+}
+
+/// Returns the score of the affine gap local alignment between the sequences `u` and `v`.
+///
+/// Further, a tuple is returned containing in the first component the maximum score
+/// and in the second and third components the end coordinates of the alignment in
+/// `u` and `v`, chosen as in [`traceback_local_affine`].
+///
+/// As in [`align_local_affine`], the Gotoh algorithm is applied, though only two rows
+/// of the score matrix and the row of upper gap scores are kept. Therefore, it runs in
+/// linear memory.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::{score_local_affine, AffineOptions};
+/// let opt = AffineOptions { match_: 5, mismatch: -3, gap_open: -10, gap_extend: -1, substitution: None };
+///
+/// assert_eq!(score_local_affine("TTGCATCGAAAAGCTATT", "CCGCATCGGCTACC", &opt), (37, 16, 12));
+/// ```
+pub fn score_local_affine<U, V>(u: U, v: V, opt: &AffineOptions) -> (i32, usize, usize)
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    let gap_open = opt.gap_open as i32;
+    let gap_extend = opt.gap_extend as i32;
+
+    let mut previous = vec![0i32; v.len() + 1];
+    let mut current = vec![0i32; v.len() + 1];
+    let mut upper_gap = vec![NEG_INF; v.len() + 1];
+    let mut best = (0, 0, 0);
+
+    for (i, &a) in u.iter().enumerate() {
+        let mut left_gap = NEG_INF;
+
+        for (j, &b) in v.iter().enumerate() {
+            let diagonal = previous[j] + opt.score(a, b);
+
+            upper_gap[j + 1] = (previous[j + 1] + gap_open).max(upper_gap[j + 1] + gap_extend);
+            left_gap = (current[j] + gap_open).max(left_gap + gap_extend);
+
+            current[j + 1] = diagonal.max(upper_gap[j + 1]).max(left_gap).max(0);
+
+            if current[j + 1] > best.0 {
+                best = (current[j + 1], i + 1, j + 1);
+            }
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    best
 }
@@ -1,6 +1,43 @@
+pub mod alignment;
+pub mod banded;
+pub mod cigar;
+pub mod cooptimal;
+pub mod edit;
 pub mod lcs;
 pub mod global;
 pub mod local;
+pub mod myers;
+pub mod packed;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod scoring;
+pub mod sequence;
+pub mod striped;
+pub mod substitution;
+pub mod traceback;
+
+use std::fmt;
+
+/// The error returned when the Hamming distance cannot be computed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HammingError {
+    /// The sequences have different lengths, being them `u_len` and `v_len`.
+    LengthMismatch { u_len: usize, v_len: usize },
+}
+
+impl fmt::Display for HammingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HammingError::LengthMismatch { u_len, v_len } => write!(
+                f,
+                "Sequences must have the same length, but have {} and {}",
+                u_len, v_len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HammingError {}
 
 /// Returns the Hamming distance between the sequences `u` and `v`.
 ///
@@ -10,8 +47,9 @@ pub mod local;
 /// sequences `u` and `v`.
 ///
 /// # Examples
-/// 
+///
 /// ```
+/// # use bioinformatics::sequencing::hamming;
 /// let dist = hamming("ATGAT", "TTAGT").unwrap();
 ///
 /// assert_eq!(dist, 3);
@@ -21,20 +59,46 @@ pub mod local;
 /// the sequences `ATGAT` and `TTAGT` is `3`, since the first, third
 /// and fourth letters differs.
 ///
-/// Further, it is returned a [`Result<usize, String>`] that will contain
+/// The sequences are compared 8 bytes at a time in `O(n)` time. For long DNA
+/// sequences, the [`PackedDna`](packed::PackedDna) sequences compare 32
+/// nucleotides at a time instead.
+///
+/// Further, it is returned a [`Result<usize, HammingError>`] that will contain
 /// an error if the sequences length differ.
-pub fn hamming(u: &str, v: &str) -> Result<usize, String> {
+pub fn hamming<U, V>(u: U, v: V) -> Result<usize, HammingError>
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
     if u.len() != v.len() {
-        Err(String::from("Strings must have the same length"))
+        Err(HammingError::LengthMismatch {
+            u_len: u.len(),
+            v_len: v.len(),
+        })
     } else {
-        let mut distance = 0usize;
+        // It compares 8 bytes at a time, folding the bits of each byte of the
+        // difference into its lowest bit, so that the differing bytes are
+        // counted with a popcount.
+        let (u_words, v_words) = (u.chunks_exact(8), v.chunks_exact(8));
+        let (u_rest, v_rest) = (u_words.remainder(), v_words.remainder());
 
-        for i in 0..u.len() {
-            if u.chars().nth(i).unwrap() != v.chars().nth(i).unwrap() {
-                distance += 1usize;
-            }
-        }
+        let words: usize = u_words
+            .zip(v_words)
+            .map(|(a, b)| {
+                let a = u64::from_le_bytes(a.try_into().unwrap());
+                let b = u64::from_le_bytes(b.try_into().unwrap());
+
+                let mut x = a ^ b;
+                x |= x >> 4;
+                x |= x >> 2;
+                x |= x >> 1;
+
+                (x & 0x0101_0101_0101_0101).count_ones() as usize
+            })
+            .sum();
 
-        Ok(distance)
+        Ok(words + u_rest.iter().zip(v_rest).filter(|(a, b)| a != b).count())
     }
 }
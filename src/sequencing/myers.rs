@@ -0,0 +1,208 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+/// The bit-vector representation of a pattern.
+///
+/// The pattern is split in blocks of 64 residues, each of them stored in the
+/// bits of a word, so that a whole column of the edit distance matrix is
+/// calculated with a few bitwise operations per block.
+struct Pattern {
+    /// It stores, for each block and each residue, the bits of the block
+    /// positions in which the residue is found in the pattern.
+    peq: Vec<[u64; 256]>,
+
+    /// It stores the bit of the last position of the pattern in the last block.
+    last: u64,
+}
+
+impl Pattern {
+    /// Returns the blocks of the `pattern`, in which the residue `i` is the bit
+    /// `i % 64` of the block `i / 64`.
+    fn new(pattern: &[u8]) -> Self {
+        let mut peq = vec![[0u64; 256]; pattern.len().div_ceil(64)];
+
+        for (i, &c) in pattern.iter().enumerate() {
+            peq[i / 64][c as usize] |= 1 << (i % 64);
+        }
+
+        Self {
+            peq,
+            last: 1 << ((pattern.len() + 63) % 64),
+        }
+    }
+}
+
+/// The vertical deltas of a block, that is, the bits in which the score of a
+/// cell is one more (`pv`) or one less (`mv`) than the score of the cell above.
+#[derive(Clone, Copy)]
+struct Block {
+    /// It stores the bits of the cells that score one more than the cell above.
+    pv: u64,
+
+    /// It stores the bits of the cells that score one less than the cell above.
+    mv: u64,
+}
+
+impl Block {
+    /// Advances the block to the next column, in which the residues that match
+    /// the text residue are the bits of `eq`.
+    ///
+    /// The `hin` is the horizontal delta entering the block from above, that is,
+    /// the delta of the last row of the previous block, and the horizontal delta
+    /// of the row of the `top` bit is returned, following Hyyrö (2003).
+    fn advance(&mut self, eq: u64, hin: i32, top: u64) -> i32 {
+        let (pv, mv) = (self.pv, self.mv);
+        let hin_neg = u64::from(hin < 0);
+        let hin_pos = u64::from(hin > 0);
+
+        let xv = eq | mv;
+        let eq = eq | hin_neg;
+        let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+
+        let mut ph = mv | !(xh | pv);
+        let mut mh = pv & xh;
+
+        let hout = i32::from(ph & top != 0) - i32::from(mh & top != 0);
+
+        ph = (ph << 1) | hin_pos;
+        mh = (mh << 1) | hin_neg;
+
+        self.pv = mh | !(xv | ph);
+        self.mv = ph & xv;
+
+        hout
+    }
+}
+
+/// Returns the scores of the last row of the edit distance matrix between the
+/// `pattern` and every prefix of the `text`, calling `f` with the end of each
+/// prefix and its score.
+///
+/// If `search` is set, then the first row is zero, so that the pattern may start
+/// anywhere in the text, otherwise it is the distance to the empty pattern.
+fn last_row<F>(pattern: &[u8], text: &[u8], search: bool, mut f: F)
+where
+    F: FnMut(usize, usize),
+{
+    let p = Pattern::new(pattern);
+
+    let mut blocks = vec![Block { pv: !0, mv: 0 }; p.peq.len()];
+    let mut score = pattern.len();
+
+    for (j, &c) in text.iter().enumerate() {
+        let mut hin = if search { 0 } else { 1 };
+
+        for (k, block) in blocks.iter_mut().enumerate() {
+            let top = if k + 1 == p.peq.len() {
+                p.last
+            } else {
+                1 << 63
+            };
+
+            hin = block.advance(p.peq[k][c as usize], hin, top);
+        }
+
+        score = score.wrapping_add_signed(hin as isize);
+
+        f(j + 1, score);
+    }
+}
+
+/// Returns the Levenshtein distance between the sequences `u` and `v`.
+///
+/// It calculates the same distance as [`levenshtein`](crate::sequencing::edit::levenshtein),
+/// though using the bit-parallel algorithm of Myers (1999), in which each column
+/// of the edit distance matrix is calculated with a few bitwise operations for
+/// every 64 residues of `u`. Therefore, it runs in `O(⌈m / 64⌉ n)` time, in which
+/// `m` and `n` are the lengths of `u` and `v`, and it is the fastest when `u`
+/// has at most 64 residues.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::myers::myers_distance;
+/// assert_eq!(myers_distance("KITTEN", "SITTING"), 3);
+/// ```
+pub fn myers_distance<U, V>(u: U, v: V) -> usize
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let (u, v) = (u.as_ref(), v.as_ref());
+
+    if u.is_empty() {
+        return v.len();
+    }
+
+    let mut distance = u.len();
+
+    last_row(u, v, false, |_, score| distance = score);
+
+    distance
+}
+
+/// Returns every occurrence of the `pattern` in the `text` with at most `k` edits.
+///
+/// Further, a vector of tuples is returned containing in the first component the
+/// end of an occurrence in the `text`, which is exclusive, and in the second
+/// component the least edit distance between the `pattern` and any substring of
+/// the `text` ending there. Each end is reported once, in increasing order.
+///
+/// It uses the bit-parallel algorithm of Myers (1999), extended to blocks of 64
+/// residues for longer patterns, hence it runs in `O(⌈m / 64⌉ n)` time, in which
+/// `m` and `n` are the lengths of the `pattern` and the `text`.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::myers::myers_search;
+/// let occurrences = myers_search("GATTACA", "CCGATTACATTGATCACAGG", 1);
+///
+/// assert_eq!(occurrences, vec![(8, 1), (9, 0), (10, 1), (18, 1)]);
+/// ```
+///
+/// The code snippet above finds `GATTACA` ending at `9` without edits and
+/// `GATCACA` ending at `18` with a single substitution. The ends `8` and `10`
+/// are found as well, since the exact occurrence may be shortened by deleting
+/// its last residue or extended by inserting the next one.
+pub fn myers_search<P, T>(pattern: P, text: T, k: usize) -> Vec<(usize, usize)>
+where
+    P: AsRef<[u8]>,
+    T: AsRef<[u8]>,
+{
+    let (pattern, text) = (pattern.as_ref(), text.as_ref());
+
+    if pattern.is_empty() {
+        return (1..=text.len()).map(|end| (end, 0)).collect();
+    }
+
+    let mut occurrences = Vec::new();
+
+    last_row(pattern, text, true, |end, score| {
+        if score <= k {
+            occurrences.push((end, score));
+        }
+    });
+
+    occurrences
+}
@@ -0,0 +1,157 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::sequencing::HammingError;
+use std::fmt;
+
+/// The bases in the order of their 2-bit codes.
+const BASES: &[u8; 4] = b"ACGT";
+
+/// The bits of the low bit of every 2-bit code in a word.
+const LOW_BITS: u64 = 0x5555_5555_5555_5555;
+
+/// A DNA sequence packed in 2 bits per nucleotide.
+///
+/// It stores 32 nucleotides in each word, in which the `A`, `C`, `G` and `T`
+/// bases are coded as `00`, `01`, `10` and `11`. Since the ambiguity codes
+/// cannot be represented, only the four bases are accepted.
+///
+/// Besides taking a quarter of the memory, the packed sequences are compared
+/// a word at a time, hence the [`PackedDna::hamming`] distance compares 32
+/// nucleotides with a few bitwise operations and a popcount.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::packed::PackedDna;
+/// let u = PackedDna::new("ACGTACGT").unwrap();
+/// let v = PackedDna::new("acgaacgg").unwrap();
+///
+/// assert_eq!(u.hamming(&v), Ok(2));
+/// assert_eq!(v.to_string(), "ACGAACGG");
+///
+/// assert_eq!(
+///     PackedDna::new("ACGN").unwrap_err(),
+///     "Invalid DNA base `N` at position 3"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PackedDna {
+    /// It stores the 2-bit codes of the nucleotides, the nucleotide `k` being
+    /// in the bits `2 * (k % 32)` and `2 * (k % 32) + 1` of the word `k / 32`.
+    /// The bits past the last nucleotide are zero, so they never count as a
+    /// mismatch in [`PackedDna::hamming`].
+    words: Vec<u64>,
+
+    /// It stores the amount of nucleotides.
+    len: usize,
+}
+
+impl PackedDna {
+    /// Constructs a [`PackedDna`] from the bases of `seq`, which may be in
+    /// lowercase.
+    ///
+    /// Further, it is returned a [`Result<PackedDna, String>`] that will contain
+    /// an error if `seq` has a residue other than the four bases.
+    pub fn new<T: AsRef<[u8]>>(seq: T) -> Result<Self, String> {
+        let seq = seq.as_ref();
+        let mut words = vec![0u64; seq.len().div_ceil(32)];
+
+        for (k, &c) in seq.iter().enumerate() {
+            let code = match c.to_ascii_uppercase() {
+                b'A' => 0,
+                b'C' => 1,
+                b'G' => 2,
+                b'T' => 3,
+                _ => {
+                    return Err(format!(
+                        "Invalid DNA base `{}` at position {}",
+                        c.escape_ascii(),
+                        k
+                    ))
+                }
+            };
+
+            words[k / 32] |= code << (2 * (k % 32));
+        }
+
+        Ok(Self {
+            words,
+            len: seq.len(),
+        })
+    }
+
+    /// Returns the amount of nucleotides.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the sequence has no nucleotide.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the base at the position `k`, if any.
+    pub fn get(&self, k: usize) -> Option<u8> {
+        if k < self.len {
+            Some(BASES[(self.words[k / 32] >> (2 * (k % 32))) as usize & 3])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the Hamming distance between the sequence and `other`.
+    ///
+    /// The words are compared by marking the nucleotides in which either bit
+    /// of their codes differs and counting the marks with a popcount. Since the
+    /// unused bits of the last word are zero in both sequences, they are never
+    /// counted.
+    ///
+    /// Further, it is returned a [`Result<usize, HammingError>`] that will
+    /// contain an error if the sequences length differ.
+    pub fn hamming(&self, other: &PackedDna) -> Result<usize, HammingError> {
+        if self.len != other.len {
+            return Err(HammingError::LengthMismatch {
+                u_len: self.len,
+                v_len: other.len,
+            });
+        }
+
+        Ok(self
+            .words
+            .iter()
+            .zip(other.words.iter())
+            .map(|(a, b)| {
+                let x = a ^ b;
+                ((x | (x >> 1)) & LOW_BITS).count_ones() as usize
+            })
+            .sum())
+    }
+}
+
+impl fmt::Display for PackedDna {
+    /// Formats the bases of the sequence.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (0..self.len).try_for_each(|k| write!(f, "{}", char::from(self.get(k).unwrap())))
+    }
+}
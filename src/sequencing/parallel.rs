@@ -0,0 +1,171 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::sequencing::alignment::Alignment;
+use crate::sequencing::{global, local};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Applies the function `f` to every pair of sequences using `threads` threads.
+///
+/// The pairs are distributed dynamically, that is, each thread takes the next
+/// pair not yet processed as soon as it finishes the previous one, hence pairs
+/// of very different lengths are balanced among the threads. Nevertheless, the
+/// results are returned in the same order as the pairs, regardless of the
+/// order in which they were computed.
+///
+/// If `threads` is `0`, then the available parallelism of the machine is used.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::parallel::map_pairs;
+/// let pairs = vec![("ATGAT", "TTAGT"), ("ACGT", "ACGT"), ("AAAA", "TTTT")];
+///
+/// let distances = map_pairs(&pairs, 2, |u, v| bioinformatics::sequencing::hamming(u, v).unwrap());
+///
+/// assert_eq!(distances, vec![3, 0, 4]);
+/// ```
+///
+/// # Panics
+///
+/// It panics if `f` panics for any pair.
+pub fn map_pairs<U, V, T, F>(pairs: &[(U, V)], threads: usize, f: F) -> Vec<T>
+where
+    U: AsRef<[u8]> + Sync,
+    V: AsRef<[u8]> + Sync,
+    T: Send,
+    F: Fn(&[u8], &[u8]) -> T + Sync,
+{
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+    .min(pairs.len())
+    .max(1);
+
+    let next = AtomicUsize::new(0);
+
+    let mut results = thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+
+                    loop {
+                        let k = next.fetch_add(1, Ordering::Relaxed);
+
+                        if k >= pairs.len() {
+                            break;
+                        }
+
+                        let (u, v) = &pairs[k];
+                        results.push((k, f(u.as_ref(), v.as_ref())));
+                    }
+
+                    results
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|err| std::panic::resume_unwind(err))
+            })
+            .collect::<Vec<(usize, T)>>()
+    });
+
+    // It restores the order of the pairs.
+    results.sort_unstable_by_key(|&(k, _)| k);
+
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Align every pair of sequences globally using `threads` threads.
+///
+/// It returns the [`Alignment`] of each pair, in the same order as the pairs,
+/// as built by [`global::align_global`] and [`global::traceback_global`].
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::Options;
+/// # use bioinformatics::sequencing::parallel::align_global_batch;
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let pairs = vec![("AAAGATACCA", "GGGACTTAGA"), ("ACGT", "ACGT")];
+///
+/// let alignments = align_global_batch(&pairs, &opt, 0);
+///
+/// assert_eq!(alignments[0].score, 0);
+/// assert_eq!(alignments[1].score, 20);
+/// ```
+pub fn align_global_batch<U, V>(
+    pairs: &[(U, V)],
+    opt: &global::Options,
+    threads: usize,
+) -> Vec<Alignment>
+where
+    U: AsRef<[u8]> + Sync,
+    V: AsRef<[u8]> + Sync,
+{
+    map_pairs(pairs, threads, |u, v| {
+        let (s, b) = global::align_global(u, v, opt);
+        global::traceback_global(&s, &b, u, v)
+    })
+}
+
+/// Align every pair of sequences locally using `threads` threads.
+///
+/// It returns the [`Alignment`] of each pair, in the same order as the pairs,
+/// as built by [`local::align_local`] and [`local::traceback_local`].
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::Options;
+/// # use bioinformatics::sequencing::parallel::align_local_batch;
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let pairs = vec![("GGAGACCATTATG", "CCAATATG"), ("AAAA", "TTTT")];
+///
+/// let alignments = align_local_batch(&pairs, &opt, 2);
+///
+/// assert_eq!(alignments[0].score, 32);
+/// assert_eq!(alignments[1].score, 0);
+/// ```
+pub fn align_local_batch<U, V>(
+    pairs: &[(U, V)],
+    opt: &local::Options,
+    threads: usize,
+) -> Vec<Alignment>
+where
+    U: AsRef<[u8]> + Sync,
+    V: AsRef<[u8]> + Sync,
+{
+    map_pairs(pairs, threads, |u, v| {
+        let (s, b) = local::align_local(u, v, opt);
+        local::traceback_local(&s, &b, u, v)
+    })
+}
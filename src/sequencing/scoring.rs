@@ -0,0 +1,80 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::math::matrix::Matrix;
+use crate::sequencing::substitution::SubstitutionMatrix;
+use crate::sequencing::traceback::Traceback;
+
+/// A score small enough to represent an impossible state without overflowing
+/// when the gap points are added to it.
+pub(crate) const NEG_INF: i32 = i32::MIN / 2;
+
+/// Returns the score of aligning the residue `a` against the residue `b`.
+///
+/// If a `substitution` matrix is specified, then it scores the residues,
+/// otherwise they are scored with the `match_` or the `mismatch` point.
+pub(crate) fn residue_score(
+    substitution: Option<&SubstitutionMatrix>,
+    match_: i8,
+    mismatch: i8,
+    a: u8,
+    b: u8,
+) -> i32 {
+    match substitution {
+        Some(matrix) => matrix.score(a, b),
+        None if a == b => match_ as i32,
+        None => mismatch as i32,
+    }
+}
+
+/// Returns the highest score of aligning two residues, as scored by [`residue_score`].
+pub(crate) fn max_residue_score(
+    substitution: Option<&SubstitutionMatrix>,
+    match_: i8,
+    mismatch: i8,
+) -> i32 {
+    match substitution {
+        Some(matrix) => matrix.max_score(),
+        None => (match_ as i32).max(mismatch as i32),
+    }
+}
+
+/// Affine Gap Traceback.
+///
+/// Since an affine gap alignment is calculated using three matrices, a single
+/// [`Traceback`] matrix is not enough to backtrack it. Therefore, besides the
+/// [`Traceback`] matrix of the best state in each cell, it is stored whether the
+/// gaps ending in each cell extend a previous gap or open a new one.
+pub struct AffineTraceback {
+    /// It stores the optimal states of each cell, in which [`Traceback::DIAGONAL`]
+    /// is the aligned residues state, [`Traceback::UP`] the upper gap state and
+    /// [`Traceback::LEFT`] the left gap state. In a local alignment, the cells in
+    /// which the score is reset to zero have no state.
+    pub b: Matrix<Traceback>,
+
+    /// It stores whether the upper gap ending in each cell extends a previous gap.
+    pub up_extend: Matrix<bool>,
+
+    /// It stores whether the left gap ending in each cell extends a previous gap.
+    pub left_extend: Matrix<bool>,
+}
@@ -0,0 +1,341 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::sequencing::alignment::Alignment;
+use std::fmt;
+use std::str::FromStr;
+
+/// The IUPAC DNA codes, that is, the four bases and the ambiguity codes.
+const DNA: &[u8] = b"ACGTRYSWKMBDHVN";
+
+/// The IUPAC RNA codes, that is, the four bases and the ambiguity codes.
+const RNA: &[u8] = b"ACGURYSWKMBDHVN";
+
+/// The IUPAC amino acid codes, including the ambiguity codes `B`, `Z`, `J`
+/// and `X`, the non-standard amino acids `U` and `O` and the stop `*`.
+const AMINO_ACIDS: &[u8] = b"ACDEFGHIKLMNPQRSTVWYBZJXUO*";
+
+/// The IUPAC nucleotide codes that stand for a single base.
+const BASES: &[u8] = b"ACGTU";
+
+/// The strand of a nucleotide sequence.
+///
+/// It specifies whether a sequence is aligned as it is ([`Strand::Forward`])
+/// or as its reverse complement ([`Strand::Reverse`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strand {
+    /// The sequence as it is, that is, the `+` strand.
+    Forward,
+
+    /// The reverse complement of the sequence, that is, the `-` strand.
+    Reverse,
+}
+
+impl Strand {
+    /// Returns the symbol of the strand, that is, `+` or `-`.
+    pub fn to_char(self) -> char {
+        match self {
+            Strand::Forward => '+',
+            Strand::Reverse => '-',
+        }
+    }
+}
+
+/// Returns the complement of the IUPAC nucleotide code `c`, in which `U` is
+/// complemented to `A`, or `c` itself if it is not a nucleotide code.
+///
+/// The ambiguity codes are complemented to the code of the complementary
+/// bases, e.g. `R` (`A` or `G`) to `Y` (`T` or `C`), and the case of `c` is
+/// kept.
+fn complement(c: u8) -> u8 {
+    let complement = match c.to_ascii_uppercase() {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' | b'U' => b'A',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        other => other,
+    };
+
+    if c.is_ascii_lowercase() {
+        complement.to_ascii_lowercase()
+    } else {
+        complement
+    }
+}
+
+/// Returns the reverse complement of the DNA sequence `seq`.
+///
+/// Each residue is complemented as an IUPAC nucleotide code, keeping its case,
+/// while the other residues (e.g. gaps) are kept as they are. Since the result
+/// is a DNA sequence, `U` is complemented to `A` and no residue to `U`; the
+/// [`RnaSeq::reverse_complement`] keeps the RNA alphabet instead.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::sequence::reverse_complement;
+/// assert_eq!(reverse_complement("AACGTn"), b"nACGTT");
+/// assert_eq!(reverse_complement(b"RYKMBVDH-"), b"-DHBVKMRY");
+/// ```
+pub fn reverse_complement<T: AsRef<[u8]>>(seq: T) -> Vec<u8> {
+    seq.as_ref().iter().rev().map(|&c| complement(c)).collect()
+}
+
+/// Returns the best of the alignments of the forward and of the reverse strand
+/// of `u`, built by `align`, together with its strand.
+///
+/// The forward strand is preferred if both alignments have the same score.
+pub(crate) fn align_stranded<F>(u: &[u8], align: F) -> (Alignment, Strand)
+where
+    F: Fn(&[u8]) -> Alignment,
+{
+    let forward = align(u);
+    let reverse = align(&reverse_complement(u));
+
+    if reverse.score > forward.score {
+        (reverse, Strand::Reverse)
+    } else {
+        (forward, Strand::Forward)
+    }
+}
+
+/// Returns the residues of `text` in uppercase, checking that each one of them
+/// is in the `alphabet` of the `name` sequences.
+///
+/// Further, it is returned a [`Result<Vec<u8>, String>`] that will contain an
+/// error naming the first invalid residue and its zero-based position.
+fn normalize(text: &[u8], name: &str, alphabet: &[u8]) -> Result<Vec<u8>, String> {
+    text.iter()
+        .enumerate()
+        .map(|(k, &c)| {
+            let residue = c.to_ascii_uppercase();
+
+            if alphabet.contains(&residue) {
+                Ok(residue)
+            } else {
+                Err(format!(
+                    "Invalid {} residue `{}` at position {}",
+                    name,
+                    c.escape_ascii(),
+                    k
+                ))
+            }
+        })
+        .collect()
+}
+
+/// Implements the constructor, the accessors, [`AsRef<[u8]>`], [`FromStr`] and
+/// [`fmt::Display`] of the sequence type `$seq`, whose residues must be in the
+/// `$alphabet` of the `$name` sequences, that is, the IUPAC `$codes` codes.
+macro_rules! sequence_type {
+    ($seq:ident, $name:literal, $alphabet:expr, $codes:literal) => {
+        impl $seq {
+            #[doc = concat!("Constructs a new [`", stringify!($seq), "`] from the residues in `text`, in uppercase.")]
+            ///
+            #[doc = concat!("Further, it is returned a [`Result<", stringify!($seq), ", String>`] that will contain")]
+            #[doc = concat!("an error if a residue is not an IUPAC ", $codes, " code.")]
+            pub fn new<T: AsRef<[u8]>>(text: T) -> Result<Self, String> {
+                normalize(text.as_ref(), $name, $alphabet).map(Self)
+            }
+
+            /// Returns the residues of the sequence.
+            pub fn as_bytes(&self) -> &[u8] {
+                &self.0
+            }
+
+            /// Returns the length of the sequence.
+            pub fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            /// Returns `true` if the sequence has no residue.
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+        }
+
+        impl AsRef<[u8]> for $seq {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl FromStr for $seq {
+            type Err = String;
+
+            fn from_str(text: &str) -> Result<Self, String> {
+                Self::new(text)
+            }
+        }
+
+        impl fmt::Display for $seq {
+            /// Formats the residues of the sequence.
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                // It is safe to unwrap, since every residue is an ASCII character.
+                write!(f, "{}", std::str::from_utf8(&self.0).unwrap())
+            }
+        }
+    };
+}
+
+/// A DNA sequence.
+///
+/// It only contains the IUPAC nucleotide codes, that is, the bases `A`, `C`,
+/// `G` and `T` and the ambiguity codes `R`, `Y`, `S`, `W`, `K`, `M`, `B`,
+/// `D`, `H`, `V` and `N`, which are stored in uppercase.
+///
+/// Since it implements [`AsRef<[u8]>`], it may be passed directly to the
+/// `sequencing` functions.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::hamming;
+/// # use bioinformatics::sequencing::sequence::DnaSeq;
+/// let u = DnaSeq::new("acgtn").unwrap();
+/// let v = DnaSeq::new("ACGTA").unwrap();
+///
+/// assert_eq!(u.to_string(), "ACGTN");
+/// assert_eq!(hamming(&u, &v).unwrap(), 1);
+///
+/// assert_eq!(
+///     DnaSeq::new("ACGZ!").unwrap_err(),
+///     "Invalid DNA residue `Z` at position 3"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DnaSeq(Vec<u8>);
+
+sequence_type!(DnaSeq, "DNA", DNA, "nucleotide");
+
+impl DnaSeq {
+    /// Returns `true` if the sequence contains an ambiguity code.
+    pub fn is_ambiguous(&self) -> bool {
+        self.0.iter().any(|c| !BASES.contains(c))
+    }
+
+    /// Returns the reverse complement of the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::sequencing::sequence::DnaSeq;
+    /// let u = DnaSeq::new("AACGTN").unwrap();
+    ///
+    /// assert_eq!(u.reverse_complement().as_bytes(), b"NACGTT");
+    /// ```
+    pub fn reverse_complement(&self) -> DnaSeq {
+        DnaSeq(reverse_complement(&self.0))
+    }
+}
+
+/// An RNA sequence.
+///
+/// It only contains the IUPAC nucleotide codes, that is, the bases `A`, `C`,
+/// `G` and `U` and the ambiguity codes `R`, `Y`, `S`, `W`, `K`, `M`, `B`,
+/// `D`, `H`, `V` and `N`, which are stored in uppercase.
+///
+/// Since it implements [`AsRef<[u8]>`], it may be passed directly to the
+/// `sequencing` functions.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::sequence::RnaSeq;
+/// let u = RnaSeq::new("AcGu").unwrap();
+///
+/// assert_eq!(u.as_bytes(), b"ACGU");
+/// assert!(RnaSeq::new("ACGT").is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RnaSeq(Vec<u8>);
+
+sequence_type!(RnaSeq, "RNA", RNA, "nucleotide");
+
+impl RnaSeq {
+    /// Returns `true` if the sequence contains an ambiguity code.
+    pub fn is_ambiguous(&self) -> bool {
+        self.0.iter().any(|c| !BASES.contains(c))
+    }
+
+    /// Returns the reverse complement of the sequence, in which `A` is
+    /// complemented to `U`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::sequencing::sequence::RnaSeq;
+    /// let u = RnaSeq::new("AACGUR").unwrap();
+    ///
+    /// assert_eq!(u.reverse_complement().as_bytes(), b"YACGUU");
+    /// ```
+    pub fn reverse_complement(&self) -> RnaSeq {
+        RnaSeq(
+            self.0
+                .iter()
+                .rev()
+                .map(|&c| match complement(c) {
+                    b'T' => b'U',
+                    other => other,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// A protein sequence.
+///
+/// It only contains the IUPAC amino acid codes, that is, the twenty standard
+/// amino acids, the ambiguity codes `B`, `Z`, `J` and `X`, the non-standard
+/// amino acids `U` (selenocysteine) and `O` (pyrrolysine) and the stop `*`,
+/// which are stored in uppercase.
+///
+/// Since it implements [`AsRef<[u8]>`], it may be passed directly to the
+/// `sequencing` functions.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::global::{align_global, traceback_global, Options};
+/// # use bioinformatics::sequencing::sequence::ProteinSeq;
+/// # use bioinformatics::sequencing::substitution::SubstitutionMatrix;
+/// let u = ProteinSeq::new("heagawghee").unwrap();
+/// let v = ProteinSeq::new("PAWHEAE").unwrap();
+///
+/// let opt = Options { match_: 0, mismatch: 0, gap: -8, substitution: Some(SubstitutionMatrix::blosum62()) };
+/// let (s, b) = align_global(&u, &v, &opt);
+///
+/// assert_eq!(traceback_global(&s, &b, &u, &v).aligned_u, "HEAGAWGHEE");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ProteinSeq(Vec<u8>);
+
+sequence_type!(ProteinSeq, "protein", AMINO_ACIDS, "amino acid");
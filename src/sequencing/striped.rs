@@ -0,0 +1,882 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::collections::HashMap;
+
+use crate::sequencing::local::Options;
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// Striped Engine.
+///
+/// It identifies the instruction set used to run the striped alignment. The
+/// [`Engine::Portable`] engine is available on every target, while the others
+/// depend on the features of the running CPU.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Engine {
+    /// 256-bit vectors, 32 lanes of 8 bits or 16 lanes of 16 bits.
+    Avx2,
+    /// 128-bit vectors, 16 lanes of 8 bits or 8 lanes of 16 bits.
+    Sse2,
+    /// Plain arrays with the same layout as [`Engine::Sse2`].
+    Portable,
+}
+
+impl Engine {
+    /// Returns the fastest engine supported by the running CPU.
+    pub fn detect() -> Engine {
+        [Engine::Avx2, Engine::Sse2]
+            .into_iter()
+            .find(|engine| engine.is_supported())
+            .unwrap_or(Engine::Portable)
+    }
+
+    /// Returns whether the engine can run on the running CPU.
+    pub fn is_supported(self) -> bool {
+        match self {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Engine::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Engine::Sse2 => is_x86_feature_detected!("sse2"),
+            Engine::Portable => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Returns the width in bytes of the vectors used by the engine.
+    fn width(self) -> usize {
+        match self {
+            Engine::Avx2 => 32,
+            Engine::Sse2 | Engine::Portable => 16,
+        }
+    }
+}
+
+/// Striped Query Profile.
+///
+/// It stores the query scored against every reference residue in the striped
+/// layout of Farrar (2007), in which a whole vector of query residues is scored
+/// against a single reference residue at once. Since building the profile costs
+/// `O(256 m)`, it should be built once and reused when the same query is
+/// aligned against many references.
+///
+/// The scores are first computed in saturating 8-bit lanes. Whenever they
+/// saturate, the alignment is repeated in 16-bit lanes and, if those saturate
+/// as well, in a scalar 32-bit pass.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::Options;
+/// # use bioinformatics::sequencing::striped::Profile;
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+/// let profile = Profile::new("CCAATATG", &opt);
+///
+/// assert_eq!(profile.score("GGAGACCATTATG"), 32);
+/// assert_eq!(profile.score("TTTTT"), 7);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Profile {
+    /// It stores the engine that runs the 8-bit and 16-bit passes.
+    engine: Engine,
+
+    /// It stores the length of the query.
+    len: usize,
+
+    /// It stores the class of every reference residue, that is, the index of
+    /// its row in `scores`.
+    classes: [u8; 256],
+
+    /// It stores a row of `len` scores per class, one per query residue.
+    scores: Vec<i32>,
+
+    /// It stores the highest score of `scores`, used to detect saturation.
+    max_score: i32,
+
+    /// It stores the gap point.
+    gap: i32,
+
+    /// It stores the 8-bit striped profile, if the biased scores fit in `u8`
+    /// and the gap point is not positive.
+    bytes: Option<Striped<u8>>,
+
+    /// It stores the 16-bit striped profile, if the scores fit in `i16` and the
+    /// gap point is not positive.
+    words: Option<Striped<i16>>,
+}
+
+/// It stores a striped profile of `classes * segments * lanes` elements.
+#[derive(Clone, Debug)]
+struct Striped<T> {
+    /// It stores the striped scores, class by class and segment by segment.
+    elements: Vec<T>,
+
+    /// It stores the amount of vectors needed to cover the query.
+    segments: usize,
+
+    /// It stores the amount added to every score so that none is negative,
+    /// which is subtracted again after adding it to a cell.
+    bias: T,
+}
+
+impl Profile {
+    /// Returns the profile of the `query` for the fastest supported [`Engine`].
+    pub fn new<Q>(query: Q, opt: &Options) -> Profile
+    where
+        Q: AsRef<[u8]>,
+    {
+        Profile::build(query.as_ref(), opt, Engine::detect())
+    }
+
+    /// Returns the profile of the `query` for the specified [`Engine`].
+    ///
+    /// Further, it is returned a [`Result<Profile, String>`] that will contain
+    /// an error if the engine is not supported by the running CPU.
+    pub fn with_engine<Q>(query: Q, opt: &Options, engine: Engine) -> Result<Profile, String>
+    where
+        Q: AsRef<[u8]>,
+    {
+        if engine.is_supported() {
+            Ok(Profile::build(query.as_ref(), opt, engine))
+        } else {
+            Err(format!(
+                "The {:?} engine is not supported by this CPU",
+                engine
+            ))
+        }
+    }
+
+    /// Returns the engine used by the profile.
+    pub fn engine(&self) -> Engine {
+        self.engine
+    }
+
+    /// Returns the length of the query.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the query is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the profile of the `query` for the `engine`, which must be
+    /// supported by the running CPU.
+    ///
+    /// The 8-bit lanes are unsigned, hence every score is raised by the bias,
+    /// the negation of the lowest score if it is negative, and the bias is
+    /// subtracted back after each addition. The 8-bit profile is only built if the highest biased
+    /// score fits in `u8`, and the 16-bit one if every score fits in `i16`.
+    /// Without them, or with a positive gap point, the alignment is promoted
+    /// to the next wider pass when [`Profile::score`] runs.
+    fn build(query: &[u8], opt: &Options, engine: Engine) -> Profile {
+        let len = query.len();
+
+        // The reference residues that score the same against every query
+        // residue share a single row of the profile.
+        let mut classes = [0; 256];
+        let mut rows: HashMap<Vec<i32>, u8> = HashMap::new();
+        let mut scores = Vec::new();
+
+        for (b, class) in classes.iter_mut().enumerate() {
+            let row: Vec<i32> = query.iter().map(|&a| opt.score(a, b as u8)).collect();
+            let next = rows.len() as u8;
+
+            *class = *rows.entry(row).or_insert_with_key(|row| {
+                scores.extend_from_slice(row);
+                next
+            });
+        }
+
+        let max_score = scores.iter().copied().max().unwrap_or(0);
+        let min_score = scores.iter().copied().min().unwrap_or(0);
+        let gap = opt.gap as i32;
+
+        let mut profile = Profile {
+            engine,
+            len,
+            classes,
+            scores,
+            max_score,
+            gap,
+            bytes: None,
+            words: None,
+        };
+
+        // The lanes subtract the gap cost, hence a gap bonus is only
+        // handled by the scalar pass.
+        if gap <= 0 {
+            let bias = (-min_score).max(0);
+
+            if max_score + bias <= u8::MAX as i32 {
+                profile.bytes =
+                    Some(profile.stripe(engine.width(), bias as u8, |s| (s + bias) as u8));
+            }
+
+            if max_score <= i16::MAX as i32 && min_score >= i16::MIN as i32 {
+                profile.words = Some(profile.stripe(engine.width() / 2, 0, |s| s as i16));
+            }
+        }
+
+        profile
+    }
+
+    /// Returns the striped layout of the scores, in which the lane `k` of the
+    /// segment `j` holds the query residue `j + k * segments`. The padding past
+    /// the end of the query holds the lowest element, so it never raises the
+    /// score.
+    fn stripe<T, F>(&self, lanes: usize, bias: T, convert: F) -> Striped<T>
+    where
+        T: Copy + Default,
+        F: Fn(i32) -> T,
+    {
+        let segments = self.len.div_ceil(lanes).max(1);
+        let classes = self.scores.len().checked_div(self.len).unwrap_or(0);
+        let mut elements = vec![T::default(); classes * segments * lanes];
+
+        for c in 0..classes {
+            let row = &self.scores[c * self.len..(c + 1) * self.len];
+
+            for j in 0..segments {
+                for k in 0..lanes {
+                    if let Some(&s) = row.get(j + k * segments) {
+                        elements[(c * segments + j) * lanes + k] = convert(s);
+                    }
+                }
+            }
+        }
+
+        Striped {
+            elements,
+            segments,
+            bias,
+        }
+    }
+
+    /// Returns the local alignment score of the query against the `reference`.
+    ///
+    /// The score is the same as the maximum of the scoring matrix returned by
+    /// [`align_local`](crate::sequencing::local::align_local) with the query
+    /// as `u` and the `reference` as `v`.
+    pub fn score<R>(&self, reference: R) -> i32
+    where
+        R: AsRef<[u8]>,
+    {
+        let reference = reference.as_ref();
+
+        if self.len == 0 || reference.is_empty() || self.max_score <= 0 {
+            return 0;
+        }
+
+        let cost = -self.gap;
+
+        if let Some(profile) = &self.bytes {
+            let limit = u8::MAX as i32 - (self.max_score + profile.bias as i32);
+            let score = self.score_bytes(profile, reference, cost as u8);
+
+            if score <= limit {
+                return score;
+            }
+        }
+
+        if let Some(profile) = &self.words {
+            let limit = i16::MAX as i32 - self.max_score;
+            let score = self.score_words(profile, reference, cost as i16);
+
+            if score <= limit {
+                return score;
+            }
+        }
+
+        self.score_scalar(reference)
+    }
+
+    /// Returns the score computed in saturating 8-bit lanes.
+    fn score_bytes(&self, profile: &Striped<u8>, reference: &[u8], gap: u8) -> i32 {
+        let (p, classes) = (profile, &self.classes);
+
+        // SAFETY: the profile is only built for engines supported by the CPU.
+        let score = unsafe {
+            match self.engine {
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Engine::Avx2 => avx2_bytes(p, classes, reference, gap),
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Engine::Sse2 => sse2_bytes(p, classes, reference, gap),
+                _ => striped::<[u8; 16]>(p, classes, reference, gap),
+            }
+        };
+
+        score as i32
+    }
+
+    /// Returns the score computed in saturating 16-bit lanes.
+    fn score_words(&self, profile: &Striped<i16>, reference: &[u8], gap: i16) -> i32 {
+        let (p, classes) = (profile, &self.classes);
+
+        // SAFETY: the profile is only built for engines supported by the CPU.
+        let score = unsafe {
+            match self.engine {
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Engine::Avx2 => avx2_words(p, classes, reference, gap),
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Engine::Sse2 => sse2_words(p, classes, reference, gap),
+                _ => striped::<[i16; 8]>(p, classes, reference, gap),
+            }
+        };
+
+        score as i32
+    }
+
+    /// Returns the score computed in 32-bit integers, one cell at a time.
+    fn score_scalar(&self, reference: &[u8]) -> i32 {
+        let gap = self.gap;
+        let mut row = vec![0; self.len + 1];
+        let mut max = 0;
+
+        for &r in reference {
+            let class = self.classes[r as usize] as usize;
+            let scores = &self.scores[class * self.len..(class + 1) * self.len];
+            let mut diagonal = 0;
+
+            for (i, &s) in scores.iter().enumerate() {
+                let h = (diagonal + s)
+                    .max(row[i + 1] + gap)
+                    .max(row[i] + gap)
+                    .max(0);
+
+                diagonal = row[i + 1];
+                row[i + 1] = h;
+                max = max.max(h);
+            }
+        }
+
+        max
+    }
+}
+
+/// Returns the local alignment score between the sequences `u` and `v`.
+///
+/// It computes the same score as the maximum of the scoring matrix returned
+/// by [`align_local`](crate::sequencing::local::align_local), though without
+/// building any matrix. The sequence `u` is laid out in a striped [`Profile`],
+/// therefore, when aligning the same `u` against many sequences, the profile
+/// should be built once and reused instead.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::local::Options;
+/// # use bioinformatics::sequencing::striped::align_local_striped;
+/// let opt = Options { match_: 5, mismatch: -3, gap: -4, substitution: None };
+///
+/// assert_eq!(align_local_striped("GGAGACCATTATG", "CCAATATG", &opt), 32);
+/// ```
+pub fn align_local_striped<U, V>(u: U, v: V, opt: &Options) -> i32
+where
+    U: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    Profile::new(u, opt).score(v)
+}
+
+/// Vector of saturating lanes.
+///
+/// It abstracts the handful of operations the striped algorithm needs, so the
+/// same algorithm runs over every engine and lane width.
+trait Lanes: Copy {
+    /// The element of a single lane.
+    type Element: Copy + Default + Ord;
+
+    /// The amount of lanes of the vector.
+    const LANES: usize;
+
+    /// Returns a vector with every lane set to `x`.
+    unsafe fn splat(x: Self::Element) -> Self;
+
+    /// Returns the vector of the `LANES` elements starting at `p`, which may be
+    /// unaligned.
+    unsafe fn load(p: *const Self::Element) -> Self;
+
+    /// Returns the lane-wise saturating sum of `self` and `other`.
+    unsafe fn adds(self, other: Self) -> Self;
+
+    /// Returns the lane-wise saturating difference of `self` and `other`.
+    unsafe fn subs(self, other: Self) -> Self;
+
+    /// Returns the lane-wise maximum of `self` and `other`.
+    unsafe fn max(self, other: Self) -> Self;
+
+    /// Moves every element to the next lane, the first lane becoming zero.
+    unsafe fn shift(self) -> Self;
+
+    /// Returns whether any lane of `self` is greater than that of `other`.
+    unsafe fn any_gt(self, other: Self) -> bool;
+
+    /// Returns the highest element across the lanes.
+    unsafe fn horizontal_max(self) -> Self::Element;
+
+    /// Returns the diagonal score `h` plus the profile score `p`, floored at
+    /// zero. The 8-bit lanes subtract the `bias` the profile scores were
+    /// raised by, while the 16-bit lanes ignore it.
+    unsafe fn diagonal(h: Self, p: Self, bias: Self) -> Self;
+}
+
+/// Returns the highest score of the striped alignment of the `profile`
+/// against the `reference`, saturated to the range of the lanes.
+#[inline(always)]
+unsafe fn striped<L: Lanes>(
+    profile: &Striped<L::Element>,
+    classes: &[u8; 256],
+    reference: &[u8],
+    gap: L::Element,
+) -> L::Element {
+    let segments = profile.segments;
+    let zero = L::splat(L::Element::default());
+    let (gap, bias) = (L::splat(gap), L::splat(profile.bias));
+
+    let mut store = vec![zero; segments];
+    let mut load = vec![zero; segments];
+    let mut e = vec![zero; segments];
+    let mut max = zero;
+
+    for &r in reference {
+        let offset = classes[r as usize] as usize * segments * L::LANES;
+        let p = profile.elements[offset..].as_ptr();
+
+        let mut f = zero;
+        let mut h = store[segments - 1].shift();
+        std::mem::swap(&mut store, &mut load);
+
+        for j in 0..segments {
+            h = L::diagonal(h, L::load(p.add(j * L::LANES)), bias);
+            h = h.max(e[j]).max(f);
+            max = max.max(h);
+            store[j] = h;
+
+            h = h.subs(gap);
+            e[j] = e[j].subs(gap).max(h);
+            f = f.subs(gap).max(h);
+            h = load[j];
+        }
+
+        // Lazy-F loop: propagates the vertical gaps across the segments until
+        // they can no longer improve any cell. The cells improved by a gap
+        // never exceed the maximum, though they may open a horizontal gap.
+        // Since the cells are floored at zero, so is the comparison, otherwise
+        // the zero shifted into the first lane would never stop the loop.
+        f = f.shift();
+        let mut j = 0;
+
+        while f.any_gt(store[j].subs(gap).max(zero)) {
+            store[j] = store[j].max(f);
+            e[j] = e[j].max(store[j].subs(gap));
+            f = f.subs(gap);
+
+            j += 1;
+
+            if j == segments {
+                j = 0;
+                f = f.shift();
+            }
+        }
+    }
+
+    max.horizontal_max()
+}
+
+impl Lanes for [u8; 16] {
+    type Element = u8;
+
+    const LANES: usize = 16;
+
+    #[inline(always)]
+    unsafe fn splat(x: u8) -> Self {
+        [x; 16]
+    }
+
+    #[inline(always)]
+    unsafe fn load(p: *const u8) -> Self {
+        std::ptr::read_unaligned(p as *const [u8; 16])
+    }
+
+    #[inline(always)]
+    unsafe fn adds(self, other: Self) -> Self {
+        std::array::from_fn(|k| self[k].saturating_add(other[k]))
+    }
+
+    #[inline(always)]
+    unsafe fn subs(self, other: Self) -> Self {
+        std::array::from_fn(|k| self[k].saturating_sub(other[k]))
+    }
+
+    #[inline(always)]
+    unsafe fn max(self, other: Self) -> Self {
+        std::array::from_fn(|k| self[k].max(other[k]))
+    }
+
+    #[inline(always)]
+    unsafe fn shift(self) -> Self {
+        std::array::from_fn(|k| if k == 0 { 0 } else { self[k - 1] })
+    }
+
+    #[inline(always)]
+    unsafe fn any_gt(self, other: Self) -> bool {
+        self.iter().zip(other.iter()).any(|(a, b)| a > b)
+    }
+
+    #[inline(always)]
+    unsafe fn horizontal_max(self) -> u8 {
+        self.into_iter().max().unwrap_or(0)
+    }
+
+    #[inline(always)]
+    unsafe fn diagonal(h: Self, p: Self, bias: Self) -> Self {
+        h.adds(p).subs(bias)
+    }
+}
+
+impl Lanes for [i16; 8] {
+    type Element = i16;
+
+    const LANES: usize = 8;
+
+    #[inline(always)]
+    unsafe fn splat(x: i16) -> Self {
+        [x; 8]
+    }
+
+    #[inline(always)]
+    unsafe fn load(p: *const i16) -> Self {
+        std::ptr::read_unaligned(p as *const [i16; 8])
+    }
+
+    #[inline(always)]
+    unsafe fn adds(self, other: Self) -> Self {
+        std::array::from_fn(|k| self[k].saturating_add(other[k]))
+    }
+
+    #[inline(always)]
+    unsafe fn subs(self, other: Self) -> Self {
+        std::array::from_fn(|k| self[k].saturating_sub(other[k]))
+    }
+
+    #[inline(always)]
+    unsafe fn max(self, other: Self) -> Self {
+        std::array::from_fn(|k| self[k].max(other[k]))
+    }
+
+    #[inline(always)]
+    unsafe fn shift(self) -> Self {
+        std::array::from_fn(|k| if k == 0 { 0 } else { self[k - 1] })
+    }
+
+    #[inline(always)]
+    unsafe fn any_gt(self, other: Self) -> bool {
+        self.iter().zip(other.iter()).any(|(a, b)| a > b)
+    }
+
+    #[inline(always)]
+    unsafe fn horizontal_max(self) -> i16 {
+        self.into_iter().max().unwrap_or(0)
+    }
+
+    #[inline(always)]
+    unsafe fn diagonal(h: Self, p: Self, _: Self) -> Self {
+        Lanes::max(h.adds(p), [0; 8])
+    }
+}
+
+/// SSE2 vector of 16 lanes of 8 bits.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[derive(Clone, Copy)]
+struct Sse2Bytes(__m128i);
+
+/// SSE2 vector of 8 lanes of 16 bits.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[derive(Clone, Copy)]
+struct Sse2Words(__m128i);
+
+/// AVX2 vector of 32 lanes of 8 bits.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[derive(Clone, Copy)]
+struct Avx2Bytes(__m256i);
+
+/// AVX2 vector of 16 lanes of 16 bits.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[derive(Clone, Copy)]
+struct Avx2Words(__m256i);
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+impl Lanes for Sse2Bytes {
+    type Element = u8;
+
+    const LANES: usize = 16;
+
+    #[inline(always)]
+    unsafe fn splat(x: u8) -> Self {
+        Sse2Bytes(_mm_set1_epi8(x as i8))
+    }
+
+    #[inline(always)]
+    unsafe fn load(p: *const u8) -> Self {
+        Sse2Bytes(_mm_loadu_si128(p as *const __m128i))
+    }
+
+    #[inline(always)]
+    unsafe fn adds(self, other: Self) -> Self {
+        Sse2Bytes(_mm_adds_epu8(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn subs(self, other: Self) -> Self {
+        Sse2Bytes(_mm_subs_epu8(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn max(self, other: Self) -> Self {
+        Sse2Bytes(_mm_max_epu8(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn shift(self) -> Self {
+        Sse2Bytes(_mm_slli_si128::<1>(self.0))
+    }
+
+    #[inline(always)]
+    unsafe fn any_gt(self, other: Self) -> bool {
+        // There is no unsigned comparison, though `a > b` if and only if the
+        // saturating difference `a - b` is not zero.
+        let difference = _mm_subs_epu8(self.0, other.0);
+        _mm_movemask_epi8(_mm_cmpeq_epi8(difference, _mm_setzero_si128())) != 0xFFFF
+    }
+
+    #[inline(always)]
+    unsafe fn horizontal_max(self) -> u8 {
+        std::mem::transmute::<__m128i, [u8; 16]>(self.0).horizontal_max()
+    }
+
+    #[inline(always)]
+    unsafe fn diagonal(h: Self, p: Self, bias: Self) -> Self {
+        h.adds(p).subs(bias)
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+impl Lanes for Sse2Words {
+    type Element = i16;
+
+    const LANES: usize = 8;
+
+    #[inline(always)]
+    unsafe fn splat(x: i16) -> Self {
+        Sse2Words(_mm_set1_epi16(x))
+    }
+
+    #[inline(always)]
+    unsafe fn load(p: *const i16) -> Self {
+        Sse2Words(_mm_loadu_si128(p as *const __m128i))
+    }
+
+    #[inline(always)]
+    unsafe fn adds(self, other: Self) -> Self {
+        Sse2Words(_mm_adds_epi16(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn subs(self, other: Self) -> Self {
+        Sse2Words(_mm_subs_epi16(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn max(self, other: Self) -> Self {
+        Sse2Words(_mm_max_epi16(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn shift(self) -> Self {
+        Sse2Words(_mm_slli_si128::<2>(self.0))
+    }
+
+    #[inline(always)]
+    unsafe fn any_gt(self, other: Self) -> bool {
+        _mm_movemask_epi8(_mm_cmpgt_epi16(self.0, other.0)) != 0
+    }
+
+    #[inline(always)]
+    unsafe fn horizontal_max(self) -> i16 {
+        std::mem::transmute::<__m128i, [i16; 8]>(self.0).horizontal_max()
+    }
+
+    #[inline(always)]
+    unsafe fn diagonal(h: Self, p: Self, _: Self) -> Self {
+        h.adds(p).max(Sse2Words(_mm_setzero_si128()))
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+impl Lanes for Avx2Bytes {
+    type Element = u8;
+
+    const LANES: usize = 32;
+
+    #[inline(always)]
+    unsafe fn splat(x: u8) -> Self {
+        Avx2Bytes(_mm256_set1_epi8(x as i8))
+    }
+
+    #[inline(always)]
+    unsafe fn load(p: *const u8) -> Self {
+        Avx2Bytes(_mm256_loadu_si256(p as *const __m256i))
+    }
+
+    #[inline(always)]
+    unsafe fn adds(self, other: Self) -> Self {
+        Avx2Bytes(_mm256_adds_epu8(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn subs(self, other: Self) -> Self {
+        Avx2Bytes(_mm256_subs_epu8(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn max(self, other: Self) -> Self {
+        Avx2Bytes(_mm256_max_epu8(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn shift(self) -> Self {
+        // The byte shifts work within each 128-bit half, so the low half is
+        // first moved into the high half to carry its last byte across.
+        let carry = _mm256_permute2x128_si256::<0x08>(self.0, self.0);
+        Avx2Bytes(_mm256_alignr_epi8::<15>(self.0, carry))
+    }
+
+    #[inline(always)]
+    unsafe fn any_gt(self, other: Self) -> bool {
+        let difference = _mm256_subs_epu8(self.0, other.0);
+        _mm256_movemask_epi8(_mm256_cmpeq_epi8(difference, _mm256_setzero_si256())) != -1
+    }
+
+    #[inline(always)]
+    unsafe fn horizontal_max(self) -> u8 {
+        std::mem::transmute::<__m256i, [u8; 32]>(self.0)
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[inline(always)]
+    unsafe fn diagonal(h: Self, p: Self, bias: Self) -> Self {
+        h.adds(p).subs(bias)
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+impl Lanes for Avx2Words {
+    type Element = i16;
+
+    const LANES: usize = 16;
+
+    #[inline(always)]
+    unsafe fn splat(x: i16) -> Self {
+        Avx2Words(_mm256_set1_epi16(x))
+    }
+
+    #[inline(always)]
+    unsafe fn load(p: *const i16) -> Self {
+        Avx2Words(_mm256_loadu_si256(p as *const __m256i))
+    }
+
+    #[inline(always)]
+    unsafe fn adds(self, other: Self) -> Self {
+        Avx2Words(_mm256_adds_epi16(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn subs(self, other: Self) -> Self {
+        Avx2Words(_mm256_subs_epi16(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn max(self, other: Self) -> Self {
+        Avx2Words(_mm256_max_epi16(self.0, other.0))
+    }
+
+    #[inline(always)]
+    unsafe fn shift(self) -> Self {
+        let carry = _mm256_permute2x128_si256::<0x08>(self.0, self.0);
+        Avx2Words(_mm256_alignr_epi8::<14>(self.0, carry))
+    }
+
+    #[inline(always)]
+    unsafe fn any_gt(self, other: Self) -> bool {
+        _mm256_movemask_epi8(_mm256_cmpgt_epi16(self.0, other.0)) != 0
+    }
+
+    #[inline(always)]
+    unsafe fn horizontal_max(self) -> i16 {
+        std::mem::transmute::<__m256i, [i16; 16]>(self.0)
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[inline(always)]
+    unsafe fn diagonal(h: Self, p: Self, _: Self) -> Self {
+        h.adds(p).max(Avx2Words(_mm256_setzero_si256()))
+    }
+}
+
+/// Returns the striped score in 8-bit SSE2 lanes.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn sse2_bytes(p: &Striped<u8>, classes: &[u8; 256], reference: &[u8], gap: u8) -> u8 {
+    striped::<Sse2Bytes>(p, classes, reference, gap)
+}
+
+/// Returns the striped score in 16-bit SSE2 lanes.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn sse2_words(p: &Striped<i16>, classes: &[u8; 256], reference: &[u8], gap: i16) -> i16 {
+    striped::<Sse2Words>(p, classes, reference, gap)
+}
+
+/// Returns the striped score in 8-bit AVX2 lanes.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn avx2_bytes(p: &Striped<u8>, classes: &[u8; 256], reference: &[u8], gap: u8) -> u8 {
+    striped::<Avx2Bytes>(p, classes, reference, gap)
+}
+
+/// Returns the striped score in 16-bit AVX2 lanes.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn avx2_words(p: &Striped<i16>, classes: &[u8; 256], reference: &[u8], gap: i16) -> i16 {
+    striped::<Avx2Words>(p, classes, reference, gap)
+}
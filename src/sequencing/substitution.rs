@@ -0,0 +1,303 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::math::matrix::Matrix;
+use std::sync::OnceLock;
+
+/// A substitution matrix, such as BLOSUM and PAM.
+///
+/// It scores the alignment of two residues according to the residues
+/// themselves, instead of only whether they are equal or not. Therefore,
+/// it allows conservative substitutions (e.g. `I` and `L`) to be scored
+/// differently from radical ones (e.g. `W` and `G`).
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::substitution::SubstitutionMatrix;
+/// let m = SubstitutionMatrix::blosum62();
+///
+/// assert_eq!(m.score(b'I', b'L'), 2);
+/// assert_eq!(m.score(b'W', b'G'), -2);
+/// ```
+///
+/// The residues are case-insensitive, and the residues that are not present
+/// in the matrix are scored as `X` if the matrix contains it or, otherwise,
+/// as the lowest score of the matrix.
+#[derive(Clone, Debug)]
+pub struct SubstitutionMatrix {
+    /// It stores the residues in the order of the matrix rows and columns.
+    alphabet: Vec<char>,
+
+    /// It stores the matrix row (and column) of each ASCII residue.
+    index: [Option<usize>; 128],
+
+    /// It stores the scores.
+    scores: Matrix<i32>,
+
+    /// It stores the matrix row (and column) used to score the residues
+    /// not present in the matrix.
+    fallback: Option<usize>,
+
+    /// It stores the lowest score of the matrix.
+    lowest: i32,
+}
+
+impl SubstitutionMatrix {
+    /// Parses a substitution matrix in the NCBI matrix text format.
+    ///
+    /// Lines starting with `#` are comments and blank lines are ignored. The
+    /// first remaining line must contain the residues of the columns, and each
+    /// of the following lines must contain the residue of the row followed by
+    /// its scores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::sequencing::substitution::SubstitutionMatrix;
+    /// let m = SubstitutionMatrix::parse(
+    ///     "# A small nucleotide matrix.
+    ///        A  C  G  T
+    ///     A  2 -3 -1 -3
+    ///     C -3  2 -3 -1
+    ///     G -1 -3  2 -3
+    ///     T -3 -1 -3  2",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(m.score(b'A', b'G'), -1);
+    /// assert_eq!(m.score(b'c', b't'), -1);
+    /// ```
+    ///
+    /// Further, it is returned a [`Result<SubstitutionMatrix, String>`] that will
+    /// contain an error if the text is not a well-formed square matrix.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        let alphabet = match lines.next() {
+            Some(header) => header
+                .split_whitespace()
+                .map(Self::parse_residue)
+                .collect::<Result<Vec<char>, String>>()?,
+            None => return Err(String::from("Substitution matrix is empty")),
+        };
+
+        let mut index = [None; 128];
+
+        for (k, &residue) in alphabet.iter().enumerate() {
+            if index[residue as usize].is_some() {
+                return Err(format!("Residue `{}` is repeated in the header", residue));
+            }
+
+            index[residue as usize] = Some(k);
+        }
+
+        let mut scores = Matrix::<i32>::new(alphabet.len(), alphabet.len(), 0i32);
+        let mut seen = vec![false; alphabet.len()];
+
+        for line in lines {
+            let mut tokens = line.split_whitespace();
+
+            // It is safe to unwrap, since blank lines have been filtered.
+            let residue = Self::parse_residue(tokens.next().unwrap())?;
+
+            let i = match index[residue as usize] {
+                Some(i) => i,
+                None => return Err(format!("Residue `{}` is not in the header", residue)),
+            };
+
+            if seen[i] {
+                return Err(format!("Residue `{}` has more than one row", residue));
+            }
+
+            seen[i] = true;
+
+            let row = tokens
+                .map(|token| {
+                    token
+                        .parse::<i32>()
+                        .map_err(|_| format!("Score `{}` is not an integer", token))
+                })
+                .collect::<Result<Vec<i32>, String>>()?;
+
+            if row.len() != alphabet.len() {
+                return Err(format!(
+                    "Residue `{}` has {} scores, but {} were expected",
+                    residue,
+                    row.len(),
+                    alphabet.len()
+                ));
+            }
+
+            for (j, score) in row.into_iter().enumerate() {
+                scores[(i, j)] = score;
+            }
+        }
+
+        if let Some(k) = seen.iter().position(|&s| !s) {
+            return Err(format!("Residue `{}` has no row", alphabet[k]));
+        }
+
+        let lowest = *scores.min();
+
+        Ok(Self {
+            fallback: index['X' as usize],
+            alphabet,
+            index,
+            scores,
+            lowest,
+        })
+    }
+
+    /// Returns the substitution matrix with the specified `name`, if it is
+    /// one of the built-in matrices.
+    ///
+    /// The name is case-insensitive, hence `BLOSUM62` and `blosum62` are
+    /// both accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::sequencing::substitution::SubstitutionMatrix;
+    /// let m = SubstitutionMatrix::builtin("pam250").unwrap();
+    ///
+    /// assert_eq!(m.score(b'W', b'W'), 17);
+    /// assert!(SubstitutionMatrix::builtin("BLOSUM99").is_none());
+    /// ```
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "BLOSUM45" => Some(Self::blosum45()),
+            "BLOSUM62" => Some(Self::blosum62()),
+            "BLOSUM80" => Some(Self::blosum80()),
+            "PAM30" => Some(Self::pam30()),
+            "PAM70" => Some(Self::pam70()),
+            "PAM250" => Some(Self::pam250()),
+            _ => None,
+        }
+    }
+
+    /// Returns the BLOSUM45 substitution matrix.
+    pub fn blosum45() -> Self {
+        static MATRIX: OnceLock<SubstitutionMatrix> = OnceLock::new();
+        Self::builtin_from(&MATRIX, include_str!("matrices/BLOSUM45"))
+    }
+
+    /// Returns the BLOSUM62 substitution matrix.
+    pub fn blosum62() -> Self {
+        static MATRIX: OnceLock<SubstitutionMatrix> = OnceLock::new();
+        Self::builtin_from(&MATRIX, include_str!("matrices/BLOSUM62"))
+    }
+
+    /// Returns the BLOSUM80 substitution matrix.
+    pub fn blosum80() -> Self {
+        static MATRIX: OnceLock<SubstitutionMatrix> = OnceLock::new();
+        Self::builtin_from(&MATRIX, include_str!("matrices/BLOSUM80"))
+    }
+
+    /// Returns the PAM30 substitution matrix.
+    pub fn pam30() -> Self {
+        static MATRIX: OnceLock<SubstitutionMatrix> = OnceLock::new();
+        Self::builtin_from(&MATRIX, include_str!("matrices/PAM30"))
+    }
+
+    /// Returns the PAM70 substitution matrix.
+    pub fn pam70() -> Self {
+        static MATRIX: OnceLock<SubstitutionMatrix> = OnceLock::new();
+        Self::builtin_from(&MATRIX, include_str!("matrices/PAM70"))
+    }
+
+    /// Returns the PAM250 substitution matrix.
+    pub fn pam250() -> Self {
+        static MATRIX: OnceLock<SubstitutionMatrix> = OnceLock::new();
+        Self::builtin_from(&MATRIX, include_str!("matrices/PAM250"))
+    }
+
+    /// Returns a copy of the built-in matrix stored in `cell`, that is parsed
+    /// from its `text` only the first time it is requested.
+    fn builtin_from(cell: &OnceLock<Self>, text: &str) -> Self {
+        // It is safe to unwrap, since the built-in matrices are well-formed.
+        cell.get_or_init(|| Self::parse(text).unwrap()).clone()
+    }
+
+    /// Returns the residues of the matrix in the order of its rows.
+    pub fn alphabet(&self) -> &[char] {
+        &self.alphabet
+    }
+
+    /// Returns the score of aligning the residue `a` against the residue `b`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::sequencing::substitution::SubstitutionMatrix;
+    /// let m = SubstitutionMatrix::blosum62();
+    ///
+    /// assert_eq!(m.score(b'A', b'A'), 4);
+    /// assert_eq!(m.score(b'a', b'R'), -1);
+    /// ```
+    pub fn score(&self, a: u8, b: u8) -> i32 {
+        match (self.position(a), self.position(b)) {
+            (Some(i), Some(j)) => self.scores[(i, j)],
+            _ => self.lowest,
+        }
+    }
+
+    /// Returns the highest score of the matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::sequencing::substitution::SubstitutionMatrix;
+    /// let m = SubstitutionMatrix::blosum62();
+    ///
+    /// assert_eq!(m.max_score(), 11);
+    /// ```
+    pub fn max_score(&self) -> i32 {
+        *self.scores.max()
+    }
+
+    /// Returns the matrix row (and column) of the residue `c`.
+    fn position(&self, c: u8) -> Option<usize> {
+        match self.index.get(c.to_ascii_uppercase() as usize) {
+            Some(&position) => position.or(self.fallback),
+            None => self.fallback,
+        }
+    }
+
+    /// Returns the residue represented by the `token`, that must be a single
+    /// ASCII character.
+    fn parse_residue(token: &str) -> Result<char, String> {
+        let mut chars = token.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii() => Ok(c.to_ascii_uppercase()),
+            _ => Err(format!(
+                "Residue `{}` is not a single ASCII character",
+                token
+            )),
+        }
+    }
+}
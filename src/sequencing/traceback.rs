@@ -0,0 +1,140 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2023 Ricardo Fares
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+
+/// A traceback cell, that is, the set of optimal predecessors of a cell of
+/// the dynamic programming matrix.
+///
+/// Each predecessor is stored as a bit flag, hence a cell takes a single byte
+/// and ties between the predecessors can be represented. A cell with no
+/// predecessor ([`Traceback::NONE`]) ends the backtracking, that is, it is
+/// the cell in which the alignment starts.
+///
+/// # Examples
+///
+/// ```
+/// # use bioinformatics::sequencing::traceback::Traceback;
+/// let cell = Traceback::DIAGONAL | Traceback::UP;
+///
+/// assert!(cell.contains(Traceback::UP));
+/// assert!(!cell.contains(Traceback::LEFT));
+/// assert_eq!(cell.preferred(), Traceback::DIAGONAL);
+/// ```
+///
+/// The code snippet above defines a cell whose optimal score comes both from
+/// the upper left cell and from the upper cell, in which the diagonal is the
+/// predecessor followed by the single traceback functions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Traceback(u8);
+
+impl Traceback {
+    /// The cell has no predecessor.
+    pub const NONE: Traceback = Traceback(0);
+
+    /// The optimal score of the cell comes from the upper left cell.
+    pub const DIAGONAL: Traceback = Traceback(0b001);
+
+    /// The optimal score of the cell comes from the upper cell.
+    pub const UP: Traceback = Traceback(0b010);
+
+    /// The optimal score of the cell comes from the left cell.
+    pub const LEFT: Traceback = Traceback(0b100);
+
+    /// Returns the predecessors whose candidate score is equal to the `best`
+    /// score, that is, every optimal predecessor of a cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bioinformatics::sequencing::traceback::Traceback;
+    /// let cell = Traceback::from_scores(5, 5, 1, 5);
+    ///
+    /// assert_eq!(cell, Traceback::DIAGONAL | Traceback::LEFT);
+    /// ```
+    #[inline]
+    pub fn from_scores(best: i32, diagonal: i32, upper: i32, left: i32) -> Traceback {
+        Traceback(
+            (diagonal == best) as u8 | ((upper == best) as u8) << 1 | ((left == best) as u8) << 2,
+        )
+    }
+
+    /// Returns the raw bit flags of the cell.
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns `true` if the cell has no predecessor.
+    pub fn is_none(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if every predecessor in `other` is a predecessor of the cell.
+    pub fn contains(self, other: Traceback) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Removes the predecessors in `other` from the cell.
+    pub fn remove(&mut self, other: Traceback) {
+        self.0 &= !other.0;
+    }
+
+    /// Returns the single predecessor followed by the traceback functions,
+    /// that is, the diagonal, then the upper and then the left predecessor,
+    /// or [`Traceback::NONE`] if the cell has no predecessor.
+    #[inline]
+    pub fn preferred(self) -> Traceback {
+        // It isolates the lowest set bit, which follows the preference order.
+        Traceback(self.0 & self.0.wrapping_neg())
+    }
+}
+
+impl BitOr for Traceback {
+    type Output = Traceback;
+
+    fn bitor(self, rhs: Traceback) -> Traceback {
+        Traceback(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Traceback {
+    fn bitor_assign(&mut self, rhs: Traceback) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl fmt::Display for Traceback {
+    /// Formats the preferred predecessor as `d` (diagonal), `u` (up) or `l`
+    /// (left), or as a blank space if the cell has no predecessor.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrow = match self.preferred() {
+            Traceback::DIAGONAL => 'd',
+            Traceback::UP => 'u',
+            Traceback::LEFT => 'l',
+            _ => ' ',
+        };
+
+        write!(f, "{}", arrow)
+    }
+}